@@ -0,0 +1,152 @@
+// Derivation macro library for strict encoding.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use amplify_syn::{DataInner, Field, FieldKind, Fields, Items};
+use heck::ToSnakeCase;
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use syn::{Error, Result, Type};
+
+use crate::params::{FieldAttr, StrictDerive};
+
+impl StrictDerive {
+    pub fn derive_compat_test(&self) -> Result<TokenStream2> {
+        self.check_direction("StrictEncodeCompatTest")?;
+        let crate_name = &self.conf.strict_crate;
+        let name = &self.data.name;
+        if !self.data.generics.params.is_empty() {
+            return Err(Error::new(
+                Span::call_site(),
+                "StrictEncodeCompatTest can't be derived for generic types",
+            ));
+        }
+
+        let mut tests = Vec::new();
+        match &self.data.inner {
+            DataInner::Uninhabited | DataInner::Struct(Fields::Unit) => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "StrictEncodeCompatTest must not be derived on a unit or uninhabited types",
+                ));
+            }
+            DataInner::Union(_) => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "StrictEncodeCompatTest can't be derived for Rust unions",
+                ));
+            }
+            DataInner::Struct(fields) => {
+                let value = self.compat_value(quote! { super::#name }, fields)?;
+                tests.push((ident!(roundtrip), value));
+            }
+            DataInner::Enum(variants) => {
+                for var in variants {
+                    let var_name = &var.name;
+                    let value =
+                        self.compat_value(quote! { super::#name::#var_name }, &var.fields)?;
+                    let test_name = Ident::new(
+                        &format!("roundtrip_{}", var_name.to_string().to_snake_case()),
+                        var_name.span(),
+                    );
+                    tests.push((test_name, value));
+                }
+            }
+        }
+
+        let mod_name = Ident::new(
+            &format!("__strict_compat_test_{}", name.to_string().to_snake_case()),
+            name.span(),
+        );
+        let tests = tests.into_iter().map(|(test_name, value)| {
+            quote! {
+                #[test]
+                fn #test_name() {
+                    let val: super::#name = #value;
+                    #crate_name::assert_encoding_compat(&val);
+                }
+            }
+        });
+        Ok(quote! {
+            #[cfg(test)]
+            mod #mod_name {
+                #[allow(unused_imports)]
+                use super::*;
+
+                #( #tests )*
+            }
+        })
+    }
+
+    /// Generates expression constructing the value with the given `fields`
+    /// which is expected to survive encoding round-trip: skipped and inactive
+    /// conditional fields take their default values, constant fields take
+    /// their constants and all others take their dumb values.
+    fn compat_value(&self, path: TokenStream2, fields: &Fields) -> Result<TokenStream2> {
+        Ok(match fields {
+            Fields::Unit => path,
+            Fields::Unnamed(fields) => {
+                let values = self.compat_tuple_values(fields)?;
+                quote! { #path( #( #values ),* ) }
+            }
+            Fields::Named(fields) => {
+                let mut bindings = Vec::with_capacity(fields.len());
+                let mut names = Vec::with_capacity(fields.len());
+                for named_field in &self.ordered_fields(fields)? {
+                    let attr = FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?;
+                    let name = &named_field.name;
+                    let ty = &named_field.field.ty;
+                    let value = self.compat_field(&attr, ty);
+                    let value = match &attr.when {
+                        None => value,
+                        Some(when) => quote! { if #when { #value } else { Default::default() } },
+                    };
+                    bindings.push(quote! { let #name: #ty = #value; });
+                    names.push(name.clone());
+                }
+                quote! {{
+                    #( #bindings )*
+                    #path { #( #names ),* }
+                }}
+            }
+        })
+    }
+
+    fn compat_tuple_values(&self, fields: &Items<Field>) -> Result<Vec<TokenStream2>> {
+        fields
+            .iter()
+            .map(|field| {
+                let attr = FieldAttr::with(field.attr.clone(), FieldKind::Unnamed)?;
+                Ok(self.compat_field(&attr, &field.ty))
+            })
+            .collect()
+    }
+
+    fn compat_field(&self, attr: &FieldAttr, ty: &Type) -> TokenStream2 {
+        let crate_name = &self.conf.strict_crate;
+        if attr.skip {
+            return quote! { Default::default() };
+        }
+        match (attr.const_value(ty), &attr.dumb) {
+            (Some(value), _) => value,
+            (None, Some(dumb)) => quote! { #dumb },
+            (None, None) => quote! { <#ty as #crate_name::StrictDumb>::strict_dumb() },
+        }
+    }
+}
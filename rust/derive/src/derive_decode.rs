@@ -19,16 +19,29 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use amplify_syn::{DeriveInner, EnumKind, Field, FieldKind, Fields, Items, NamedField, Variant};
+use amplify_syn::{
+    DataInner, DeriveInner, EnumKind, Field, FieldKind, Fields, Items, NamedField, Variant,
+};
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
-use syn::{Error, Result};
+use syn::{Error, LitStr, Path, Result, Type};
 
-use crate::params::{FieldAttr, StrictDerive, VariantAttr};
+use crate::params::{watch_field, CountLink, FieldAttr, StrictDerive, VariantAttr};
 
 struct DeriveDecode<'a>(&'a StrictDerive);
 
 impl StrictDerive {
     pub fn derive_decode(&self) -> Result<TokenStream2> {
+        self.check_direction("StrictDecode")?;
+        if let DataInner::Union(fields) = &self.data.inner {
+            let crate_name = &self.conf.strict_crate;
+            let field = &self.active_union_field(fields)?.name;
+            return Ok(self.derive_impl(quote! { #crate_name::StrictDecode }, quote! {
+                fn strict_decode(reader: &mut impl #crate_name::TypedRead) -> Result<Self, #crate_name::DecodeError> {
+                    use #crate_name::{TypedRead, ReadTuple};
+                    reader.read_tuple(|r| r.read_field().map(|#field| Self { #field }))
+                }
+            }));
+        }
         let res = self.data.derive(
             &self.conf.strict_crate,
             &ident!(StrictDecode),
@@ -38,50 +51,187 @@ impl StrictDerive {
     }
 }
 
+impl DeriveDecode<'_> {
+    /// Generates code reading schema hash with `read` expression and failing if
+    /// it doesn't match the hash of the current type schema, if the structure
+    /// has `schema_hash_check` attribute.
+    fn hash_check(&self, read: TokenStream2) -> TokenStream2 {
+        let crate_name = &self.0.conf.strict_crate;
+        if !self.0.conf.schema_hash_check {
+            return TokenStream2::new();
+        }
+        quote! {
+            let found: [u8; 4] = #read;
+            let expected = #crate_name::schema_hash::<Self>();
+            if found != expected {
+                return Err(#crate_name::DecodeError::SchemaHashMismatch { expected, found });
+            }
+        }
+    }
+
+    /// Generates code reading schema version with `read` expression and
+    /// failing if it doesn't match the one given in `schema_version` attribute.
+    fn version_check(&self, read: TokenStream2) -> Result<TokenStream2> {
+        let crate_name = &self.0.conf.strict_crate;
+        let Some(version) = self.0.schema_version()? else {
+            return Ok(TokenStream2::new());
+        };
+        Ok(quote! {
+            let found: u16 = #read;
+            if found != #version {
+                return Err(#crate_name::DecodeError::IncompatibleSchemaVersion {
+                    expected: #version,
+                    found,
+                });
+            }
+        })
+    }
+}
+
+/// Generates compile-time check that a field marked with `zeroize_on_drop`
+/// attribute wipes its value on drop. Nothing is zeroized by the generated
+/// decoder itself.
+fn zeroize_check(crate_name: &Path, ty: &Type) -> TokenStream2 {
+    quote! {
+        fn assert_zeroize_on_drop<T: #crate_name::zeroize::ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<#ty>();
+    }
+}
+
 fn derive_struct_fields(
+    crate_name: &Path,
     fields: &Items<NamedField>,
     self_name: TokenStream2,
 ) -> Result<TokenStream2> {
     let mut skipped = Vec::new();
     let mut field_name = Vec::with_capacity(fields.len());
-    let mut field_rename = Vec::with_capacity(fields.len());
+    let mut field_ty = Vec::with_capacity(fields.len());
+    let mut field_read = Vec::with_capacity(fields.len());
+    let mut field_value = Vec::with_capacity(fields.len());
+    let links = CountLink::with(fields)?;
     for named_field in fields {
         let attr = FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?;
 
         let name = &named_field.name;
+        let ty = &named_field.field.ty;
         let rename = attr.field_name(name);
+        let prefix_check =
+            attr.prefix_name(name)
+                .zip(attr.prefix_bytes())
+                .map(|(prefix_name, prefix)| {
+                    let len = attr.prefix_len();
+                    quote! {
+                        let expected = #prefix;
+                        let got: [u8; #len] = r.read_field(fname!(#prefix_name))?;
+                        if got != expected {
+                            return Err(#crate_name::DecodeError::InvalidFieldPrefix {
+                                field: ::std::string::String::from(#rename),
+                                expected: expected.to_vec(),
+                                got: got.to_vec(),
+                            });
+                        }
+                    }
+                });
+        let counted = links.iter().find(|link| link.items == *name);
+        let read_value = match attr.const_value(ty) {
+            None => match counted {
+                Some(CountLink {
+                    count,
+                    count_name,
+                    count_ty,
+                    ..
+                }) => quote! {{
+                    let count = <usize as ::core::convert::TryFrom<#count_ty>>::try_from(#count)
+                        .map_err(|_| #crate_name::DecodeError::DataIntegrityError(::std::format!(
+                            "value of `{}` field is not a valid number of items",
+                            #count_name,
+                        )))?;
+                    r.read_counted(fname!(#rename), count)?
+                }},
+                None => quote! { r.read_field(fname!(#rename))? },
+            },
+            Some(value) => quote! {{
+                let expected: #ty = #value;
+                let got: #ty = r.read_field(fname!(#rename))?;
+                if got != expected {
+                    return Err(#crate_name::DecodeError::ConstantMismatch {
+                        field: ::std::string::String::from(#rename),
+                        expected: ::std::format!("{expected:?}"),
+                        got: ::std::format!("{got:?}"),
+                    });
+                }
+                got
+            }},
+        };
+
+        let zeroize_check = attr.zeroize_on_drop.then(|| zeroize_check(crate_name, ty));
+        let read = quote! {{
+            #zeroize_check
+            #prefix_check
+            #read_value
+        }};
+        let read = watch_field(crate_name, &rename, ty, read);
+        let read = match &attr.when {
+            None => read,
+            Some(when) => {
+                let skip_prefix = attr
+                    .prefix_name(name)
+                    .map(|prefix_name| quote! { r.skip_field(fname!(#prefix_name)); });
+                quote! {
+                    if #when #read else {
+                        #skip_prefix
+                        r.skip_field(fname!(#rename));
+                        Default::default()
+                    }
+                }
+            }
+        };
 
         if attr.skip {
             skipped.push(quote! { #name })
         } else {
             field_name.push(quote! { #name });
-            field_rename.push(quote! { #rename });
+            field_ty.push(quote! { #ty });
+            field_read.push(read);
+            field_value.push(quote! { #name });
         }
     }
     Ok(quote! {
-        #( let #field_name = r.read_field(fname!(#field_rename))?; )*
+        #( let #field_name: #field_ty = #field_read; )*
         Ok(#self_name {
-            #(#field_name,)*
+            #(#field_value,)*
             #(#skipped: Default::default()),*
         })
     })
 }
 
-fn derive_tuple_fields(fields: &Items<Field>, self_name: TokenStream2) -> Result<TokenStream2> {
+fn derive_tuple_fields(
+    crate_name: &Path,
+    fields: &Items<Field>,
+    self_name: TokenStream2,
+) -> Result<TokenStream2> {
     let mut field_idx = Vec::with_capacity(fields.len());
+    let mut field_read = Vec::with_capacity(fields.len());
     let mut field_vars = Vec::with_capacity(fields.len());
     for (index, field) in fields.iter().enumerate() {
         let attr = FieldAttr::with(field.attr.clone(), FieldKind::Unnamed)?;
         if attr.skip {
             field_vars.push(quote! { Default::default() });
         } else {
+            let ty = &field.ty;
+            let name = LitStr::new(&index.to_string(), Span::call_site());
             let index = Ident::new(&format!("_{index}"), Span::call_site());
+            let zeroize_check = attr.zeroize_on_drop.then(|| zeroize_check(crate_name, ty));
             field_idx.push(quote! { #index });
+            field_read.push(watch_field(crate_name, &name, ty, quote! {{
+                #zeroize_check
+                r.read_field::<#ty>()?
+            }}));
             field_vars.push(quote! { #index });
         }
     }
     Ok(quote! {
-        #( let #field_idx = r.read_field()?; )*
+        #( let #field_idx = #field_read; )*
         Ok(#self_name( #( #field_vars ),* ))
     })
 }
@@ -97,11 +247,17 @@ impl DeriveInner for DeriveDecode<'_> {
 
     fn derive_struct_inner(&self, fields: &Items<NamedField>) -> Result<TokenStream2> {
         let crate_name = &self.0.conf.strict_crate;
-        let inner = derive_struct_fields(fields, quote! { Self })?;
+        let ordered = self.0.ordered_fields(fields)?;
+        self.0.discriminant_field(&ordered)?;
+        let inner = derive_struct_fields(crate_name, &ordered, quote! { Self })?;
+        let hash = self.hash_check(quote! { r.read_field(fname!("schemaHash"))? });
+        let version = self.version_check(quote! { r.read_field(fname!("schemaVersion"))? })?;
         Ok(quote! {
             fn strict_decode(reader: &mut impl #crate_name::TypedRead) -> Result<Self, #crate_name::DecodeError> {
                 use #crate_name::{TypedRead, ReadStruct, fname};
                 reader.read_struct(|r| {
+                    #hash
+                    #version
                     #inner
                 })
             }
@@ -110,11 +266,15 @@ impl DeriveInner for DeriveDecode<'_> {
 
     fn derive_tuple_inner(&self, fields: &Items<Field>) -> Result<TokenStream2> {
         let crate_name = &self.0.conf.strict_crate;
-        let inner = derive_tuple_fields(fields, quote! { Self })?;
+        let inner = derive_tuple_fields(crate_name, fields, quote! { Self })?;
+        let hash = self.hash_check(quote! { r.read_field()? });
+        let version = self.version_check(quote! { r.read_field()? })?;
         Ok(quote! {
             fn strict_decode(reader: &mut impl #crate_name::TypedRead) -> Result<Self, #crate_name::DecodeError> {
                 use #crate_name::{TypedRead, ReadTuple};
                 reader.read_tuple(|r| {
+                    #hash
+                    #version
                     #inner
                 })
             }
@@ -151,7 +311,8 @@ impl DeriveInner for DeriveDecode<'_> {
                         });
                     }
                     Fields::Unnamed(fields) => {
-                        let inner = derive_tuple_fields(fields, quote! { Self::#var_name })?;
+                        let inner =
+                            derive_tuple_fields(crate_name, fields, quote! { Self::#var_name })?;
                         read_variants.push(quote! {
                             #name => r.read_tuple(|r| {
                                 #inner
@@ -159,7 +320,8 @@ impl DeriveInner for DeriveDecode<'_> {
                         });
                     }
                     Fields::Named(fields) => {
-                        let inner = derive_struct_fields(fields, quote! { Self::#var_name })?;
+                        let inner =
+                            derive_struct_fields(crate_name, fields, quote! { Self::#var_name })?;
                         read_variants.push(quote! {
                             #name => r.read_struct(|r| {
                                 #inner
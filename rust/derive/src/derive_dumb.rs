@@ -19,7 +19,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use amplify_syn::{DeriveInner, Field, FieldKind, Items, NamedField, Variant};
+use amplify_syn::{DataInner, DeriveInner, Field, FieldKind, Items, NamedField, Variant};
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use syn::{Error, Result};
 
@@ -29,11 +29,40 @@ struct DeriveDumb<'a>(&'a StrictDerive);
 
 impl StrictDerive {
     pub fn derive_dumb(&self) -> Result<TokenStream2> {
+        if let DataInner::Union(fields) = &self.data.inner {
+            return self.derive_union_dumb(fields);
+        }
         self.data
             .derive(&self.conf.strict_crate, &ident!(StrictDumb), &DeriveDumb(self))
     }
 }
 
+impl StrictDerive {
+    fn derive_union_dumb(&self, fields: &Items<NamedField>) -> Result<TokenStream2> {
+        let crate_name = &self.conf.strict_crate;
+
+        let inner = match self.conf.dumb {
+            Some(ref dumb_expr) => quote! { #dumb_expr },
+            None => {
+                let field = self.active_union_field(fields)?;
+                let attr = FieldAttr::with(field.field.attr.clone(), FieldKind::Named)?;
+                let name = &field.name;
+                let value = match attr.dumb {
+                    None => quote! { #crate_name::StrictDumb::strict_dumb() },
+                    Some(dumb_value) => quote! { #dumb_value },
+                };
+                quote! { Self { #name: #value } }
+            }
+        };
+
+        Ok(self.derive_impl(quote! { #crate_name::StrictDumb }, quote! {
+            fn strict_dumb() -> Self {
+                #inner
+            }
+        }))
+    }
+}
+
 impl DeriveInner for DeriveDumb<'_> {
     fn derive_unit_inner(&self) -> Result<TokenStream2> {
         Ok(quote! {
@@ -60,6 +89,9 @@ impl DeriveInner for DeriveDumb<'_> {
             let attr = FieldAttr::with(named.field.attr.clone(), FieldKind::Named)?;
             let name = &named.name;
             items.push(match attr.dumb {
+                None if attr.big_array => {
+                    quote! { #name: ::core::array::from_fn(|_| StrictDumb::strict_dumb()) }
+                }
                 None => quote! { #name: StrictDumb::strict_dumb() },
                 Some(dumb_value) => quote! { #name: #dumb_value },
             });
@@ -91,6 +123,9 @@ impl DeriveInner for DeriveDumb<'_> {
         for field in fields {
             let attr = FieldAttr::with(field.attr.clone(), FieldKind::Unnamed)?;
             items.push(match attr.dumb {
+                None if attr.big_array => {
+                    quote! { ::core::array::from_fn(|_| StrictDumb::strict_dumb()) }
+                }
                 None => quote! { StrictDumb::strict_dumb() },
                 Some(dumb_value) => quote! { #dumb_value },
             });
@@ -19,16 +19,87 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use amplify_syn::{DeriveInner, EnumKind, Field, FieldKind, Fields, Items, NamedField, Variant};
+use amplify_syn::{
+    DataInner, DeriveInner, EnumKind, Field, FieldKind, Fields, Items, NamedField, Variant,
+};
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
-use syn::{Error, Index, Result};
+use quote::ToTokens;
+use syn::{Error, Index, LitStr, Path, Result};
 
-use crate::params::{FieldAttr, StrictDerive, VariantAttr};
+use crate::params::{watch_field, CountLink, FieldAttr, StrictDerive, VariantAttr};
 
 struct DeriveEncode<'a>(&'a StrictDerive);
 
+/// Generates code computing the value of each count field from the number of
+/// items in the linked field, failing if the count field was set manually to a
+/// different value.
+fn count_values(crate_name: &Path, links: &[CountLink]) -> TokenStream2 {
+    let values = links.iter().map(|link| {
+        let CountLink {
+            count,
+            count_name,
+            count_ty,
+            items,
+        } = link;
+        let var = link.count_var();
+        quote! {
+            let #var = {
+                let len = #crate_name::CountedItems::counted_items(&self.#items).len();
+                let count = <#count_ty as ::core::convert::TryFrom<usize>>::try_from(len)
+                    .map_err(|_| #crate_name::EncodeError::CountOverflow {
+                        field: ::std::string::String::from(#count_name),
+                        len,
+                    })?;
+                if self.#count != <#count_ty as ::core::default::Default>::default()
+                    && self.#count != count
+                {
+                    return Err(#crate_name::EncodeError::CountMismatch {
+                        field: ::std::string::String::from(#count_name),
+                        count: ::std::string::ToString::to_string(&self.#count),
+                        len,
+                    }
+                    .into());
+                }
+                count
+            };
+        }
+    });
+    quote! { #( #values )* }
+}
+
 impl StrictDerive {
     pub fn derive_encode(&self) -> Result<TokenStream2> {
+        self.check_direction("StrictEncode")?;
+        let mut res = self.derive_encode_inner()?;
+        if self.conf.impl_hash {
+            let crate_name = &self.conf.strict_crate;
+            res.extend(self.derive_impl(quote! { ::core::hash::Hash }, quote! {
+                fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                    let mut data = ::std::vec::Vec::new();
+                    #crate_name::strict_encode_to_writer(self, &mut data)
+                        .expect("hashed value must be strict-encodable");
+                    state.write(&data);
+                }
+            }));
+        }
+        Ok(res)
+    }
+
+    fn derive_encode_inner(&self) -> Result<TokenStream2> {
+        if let DataInner::Union(fields) = &self.data.inner {
+            let crate_name = &self.conf.strict_crate;
+            let field = &self.active_union_field(fields)?.name;
+            let check = self.active_union_check();
+            return Ok(self.derive_impl(quote! { #crate_name::StrictEncode }, quote! {
+                fn strict_encode<W: #crate_name::TypedWrite>(&self, writer: W) -> ::std::io::Result<W> {
+                    use #crate_name::{TypedWrite, WriteTuple};
+                    #check
+                    // SAFETY: the field is asserted to be always initialized by the
+                    // `ActiveUnionField` implementation
+                    writer.write_tuple::<Self>(|w| Ok(w.write_field(unsafe { &self.#field })?.complete()))
+                }
+            }));
+        }
         self.data
             .derive(&self.conf.strict_crate, &ident!(StrictEncode), &DeriveEncode(self))
     }
@@ -46,23 +117,56 @@ impl DeriveInner for DeriveEncode<'_> {
     fn derive_struct_inner(&self, fields: &Items<NamedField>) -> Result<TokenStream2> {
         let crate_name = &self.0.conf.strict_crate;
 
-        let mut orig_name = Vec::with_capacity(fields.len());
-        let mut field_name = Vec::with_capacity(fields.len());
-        for named_field in fields {
+        let ordered = self.0.ordered_fields(fields)?;
+        let links = CountLink::with(&ordered)?;
+        let mut write_fields = Vec::with_capacity(fields.len());
+        for named_field in &ordered {
             let attr = FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?;
-            if !attr.skip {
-                orig_name.push(&named_field.name);
-                field_name.push(attr.field_name(&named_field.name));
+            if attr.skip {
+                continue;
             }
+            let orig_name = &named_field.name;
+            let field_name = attr.field_name(orig_name);
+            let prefix = attr.prefix_name(orig_name).zip(attr.prefix_bytes()).map(
+                |(prefix_name, prefix)| quote! { .write_field(fname!(#prefix_name), &#prefix)? },
+            );
+            let value = match attr.const_value(&named_field.field.ty) {
+                None => match links.iter().find(|link| link.count == *orig_name) {
+                    Some(link) => link.count_var().into_token_stream(),
+                    None => quote! { self.#orig_name },
+                },
+                Some(value) => value,
+            };
+            let write = if links.iter().any(|link| link.items == *orig_name) {
+                quote! { w #prefix .write_counted(fname!(#field_name), &self.#orig_name)? }
+            } else {
+                quote! { w #prefix .write_field(fname!(#field_name), &#value)? }
+            };
+            let write = watch_field(crate_name, &field_name, &named_field.field.ty, write);
+            write_fields.push(match &attr.when {
+                None => quote! { let w = #write; },
+                Some(when) => quote! { let w = if #when { #write } else { w }; },
+            });
         }
+        let counts = count_values(crate_name, &links);
+        let discriminant = self.0.discriminant_binding(&ordered)?;
+        let hash = self.0.conf.schema_hash_check.then(|| {
+            quote! { let w = w.write_field(fname!("schemaHash"), &#crate_name::schema_hash::<Self>())?; }
+        });
+        let version = self.0.schema_version()?.map(|version| {
+            quote! { let w = w.write_field(fname!("schemaVersion"), &#version)?; }
+        });
 
         Ok(quote! {
             fn strict_encode<W: #crate_name::TypedWrite>(&self, writer: W) -> ::std::io::Result<W> {
                 use #crate_name::{TypedWrite, WriteStruct, fname};
+                #counts
+                #discriminant
                 writer.write_struct::<Self>(|w| {
-                    Ok(w
-                        #( .write_field(fname!(#field_name), &self.#orig_name)? )*
-                        .complete())
+                    #hash
+                    #version
+                    #( #write_fields )*
+                    Ok(w.complete())
                 })
             }
         })
@@ -71,22 +175,33 @@ impl DeriveInner for DeriveEncode<'_> {
     fn derive_tuple_inner(&self, fields: &Items<Field>) -> Result<TokenStream2> {
         let crate_name = &self.0.conf.strict_crate;
 
-        let no = fields.iter().enumerate().filter_map(|(index, field)| {
+        let write_fields = fields.iter().enumerate().filter_map(|(index, field)| {
             let attr = FieldAttr::with(field.attr.clone(), FieldKind::Unnamed).ok()?;
             if attr.skip {
-                None
-            } else {
-                Some(Index::from(index))
+                return None;
             }
+            let no = Index::from(index);
+            let name = LitStr::new(&index.to_string(), Span::call_site());
+            let write =
+                watch_field(crate_name, &name, &field.ty, quote! { w.write_field(&self.#no)? });
+            Some(quote! { let w = #write; })
+        });
+        let hash = self.0.conf.schema_hash_check.then(|| {
+            quote! { let w = w.write_field(&#crate_name::schema_hash::<Self>())?; }
         });
+        let version = self
+            .0
+            .schema_version()?
+            .map(|version| quote! { let w = w.write_field(&#version)?; });
 
         Ok(quote! {
             fn strict_encode<W: #crate_name::TypedWrite>(&self, writer: W) -> ::std::io::Result<W> {
                 use #crate_name::{TypedWrite, WriteTuple};
                 writer.write_tuple::<Self>(|w| {
-                    Ok(w
-                        #( .write_field(&self.#no)? )*
-                        .complete())
+                    #hash
+                    #version
+                    #( #write_fields )*
+                    Ok(w.complete())
                 })
             }
         })
@@ -134,14 +249,23 @@ impl DeriveInner for DeriveEncode<'_> {
                     Fields::Unnamed(fields) => {
                         let mut field_ty = Vec::with_capacity(fields.len());
                         let mut field_idx = Vec::with_capacity(fields.len());
+                        let mut write_fields = Vec::with_capacity(fields.len());
                         for (index, field) in fields.iter().enumerate() {
                             let attr = FieldAttr::with(field.attr.clone(), FieldKind::Unnamed)?;
 
                             if !attr.skip {
                                 let ty = &field.ty;
+                                let name = LitStr::new(&index.to_string(), Span::call_site());
                                 let index = Ident::new(&format!("_{index}"), Span::call_site());
+                                let write = watch_field(
+                                    crate_name,
+                                    &name,
+                                    ty,
+                                    quote! { w.write_field(#index)? },
+                                );
                                 field_ty.push(quote! { #ty });
                                 field_idx.push(quote! { #index });
+                                write_fields.push(quote! { let w = #write; });
                             }
                         }
                         define_variants.push(quote! {
@@ -151,7 +275,8 @@ impl DeriveInner for DeriveEncode<'_> {
                         });
                         write_variants.push(quote! {
                             Self::#var_name( #( #field_idx ),* ) => writer.write_tuple(vname!(#name), |w| {
-                                Ok(w #( .write_field(#field_idx)? )* .complete())
+                                #( #write_fields )*
+                                Ok(w.complete())
                             })?,
                         });
                     }
@@ -159,6 +284,7 @@ impl DeriveInner for DeriveEncode<'_> {
                         let mut field_ty = Vec::with_capacity(fields.len());
                         let mut field_name = Vec::with_capacity(fields.len());
                         let mut field_rename = Vec::with_capacity(fields.len());
+                        let mut write_fields = Vec::with_capacity(fields.len());
                         for named_field in fields {
                             let attr =
                                 FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?;
@@ -168,9 +294,16 @@ impl DeriveInner for DeriveEncode<'_> {
                             let rename = attr.field_name(name);
 
                             if !attr.skip {
+                                let write = watch_field(
+                                    crate_name,
+                                    &rename,
+                                    ty,
+                                    quote! { w.write_field(fname!(#rename), #name)? },
+                                );
                                 field_ty.push(quote! { #ty });
                                 field_name.push(quote! { #name });
                                 field_rename.push(quote! { fname!(#rename) });
+                                write_fields.push(quote! { let w = #write; });
                             }
                         }
 
@@ -181,7 +314,8 @@ impl DeriveInner for DeriveEncode<'_> {
                         });
                         write_variants.push(quote! {
                             Self::#var_name { #( #field_name ),* } => writer.write_struct(vname!(#name), |w| {
-                                Ok(w #( .write_field(#field_rename, #field_name)? )* .complete())
+                                #( #write_fields )*
+                                Ok(w.complete())
                             })?,
                         });
                     }
@@ -0,0 +1,204 @@
+// Derivation macro library for strict encoding.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use amplify_syn::{
+    DataInner, DeriveInner, EnumKind, Field, FieldKind, Fields, Items, NamedField, Variant,
+};
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use syn::{Error, Index, Result};
+
+use crate::params::{CountLink, FieldAttr, StrictDerive};
+
+struct DeriveSize<'a>(&'a StrictDerive);
+
+impl StrictDerive {
+    pub fn derive_size(&self) -> Result<TokenStream2> {
+        if let DataInner::Union(fields) = &self.data.inner {
+            let crate_name = &self.conf.strict_crate;
+            let field = &self.active_union_field(fields)?.name;
+            let check = self.active_union_check();
+            return Ok(self.derive_impl(quote! { #crate_name::StrictEncodeSize }, quote! {
+                fn encoded_size(&self) -> usize {
+                    #check
+                    // SAFETY: the field is asserted to be always initialized by the
+                    // `ActiveUnionField` implementation
+                    #crate_name::StrictEncodeSize::encoded_size(unsafe { &self.#field })
+                }
+            }));
+        }
+        self.data
+            .derive(&self.conf.strict_crate, &ident!(StrictEncodeSize), &DeriveSize(self))
+    }
+}
+
+impl DeriveSize<'_> {
+    /// Generates expression summing encoded sizes of all the `values`, plus
+    /// the sizes of the schema hash and version, if any.
+    fn sum(&self, values: Vec<TokenStream2>) -> Result<TokenStream2> {
+        let crate_name = &self.0.conf.strict_crate;
+        let hash = self.0.conf.schema_hash_check.then(|| quote! { 4usize + });
+        let version = self.0.schema_version()?.map(|_| quote! { 2usize + });
+        Ok(quote! {
+            #hash #version 0usize #( + #crate_name::StrictEncodeSize::encoded_size(#values) )*
+        })
+    }
+}
+
+impl DeriveInner for DeriveSize<'_> {
+    fn derive_unit_inner(&self) -> Result<TokenStream2> {
+        Err(Error::new(
+            Span::call_site(),
+            "StrictEncodeSize must not be derived on a unit types. Use just a unit type instead \
+             when encoding parent structure.",
+        ))
+    }
+
+    fn derive_struct_inner(&self, fields: &Items<NamedField>) -> Result<TokenStream2> {
+        let crate_name = &self.0.conf.strict_crate;
+        let ordered = self.0.ordered_fields(fields)?;
+        let links = CountLink::with(&ordered)?;
+        let mut values = Vec::with_capacity(fields.len());
+        let mut counted = Vec::new();
+        let mut conditional = Vec::new();
+        for named_field in fields {
+            let attr = FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?;
+            if attr.skip {
+                continue;
+            }
+            let name = &named_field.name;
+            let mut field_values = Vec::with_capacity(2);
+            field_values.extend(attr.prefix_bytes().map(|prefix| quote! { &#prefix }));
+            match attr.const_value(&named_field.field.ty) {
+                // counted items are written without the collection length prefix
+                None if links.iter().any(|link| link.items == *name) => counted.push(quote! {
+                    #crate_name::CountedItems::counted_items(&self.#name)
+                        .iter()
+                        .map(#crate_name::StrictEncodeSize::encoded_size)
+                        .sum::<usize>()
+                }),
+                None => field_values.push(quote! { &self.#name }),
+                Some(value) => field_values.push(quote! { &#value }),
+            }
+            match &attr.when {
+                None => values.extend(field_values),
+                Some(when) => conditional.push(quote! {
+                    if #when {
+                        0usize #( + #crate_name::StrictEncodeSize::encoded_size(#field_values) )*
+                    } else {
+                        0
+                    }
+                }),
+            }
+        }
+        let sum = self.sum(values)?;
+        let discriminant = self.0.discriminant_binding(&ordered)?;
+
+        Ok(quote! {
+            fn encoded_size(&self) -> usize {
+                #discriminant
+                #sum #( + #counted )* #( + #conditional )*
+            }
+        })
+    }
+
+    fn derive_tuple_inner(&self, fields: &Items<Field>) -> Result<TokenStream2> {
+        let mut values = Vec::with_capacity(fields.len());
+        for (index, field) in fields.iter().enumerate() {
+            let attr = FieldAttr::with(field.attr.clone(), FieldKind::Unnamed)?;
+            if !attr.skip {
+                let index = Index::from(index);
+                values.push(quote! { &self.#index });
+            }
+        }
+        let sum = self.sum(values)?;
+
+        Ok(quote! {
+            fn encoded_size(&self) -> usize {
+                #sum
+            }
+        })
+    }
+
+    fn derive_enum_inner(&self, variants: &Items<Variant>) -> Result<TokenStream2> {
+        let crate_name = &self.0.conf.strict_crate;
+
+        if variants.enum_kind() == EnumKind::Primitive {
+            return Ok(quote! {
+                fn encoded_size(&self) -> usize {
+                    1
+                }
+            });
+        }
+
+        let mut arms = Vec::with_capacity(variants.len());
+        for var in variants {
+            let var_name = &var.name;
+            let mut bindings = Vec::new();
+            let mut values = Vec::new();
+            match &var.fields {
+                Fields::Unit => {
+                    arms.push(quote! { Self::#var_name => 1 });
+                    continue;
+                }
+                Fields::Unnamed(fields) => {
+                    for (index, field) in fields.iter().enumerate() {
+                        let attr = FieldAttr::with(field.attr.clone(), FieldKind::Unnamed)?;
+                        let binding = Ident::new(&format!("_{index}"), Span::call_site());
+                        if !attr.skip {
+                            values.push(quote! { #binding });
+                        }
+                        bindings.push(binding);
+                    }
+                    arms.push(quote! {
+                        Self::#var_name( #( #bindings ),* ) => {
+                            1 #( + #crate_name::StrictEncodeSize::encoded_size(#values) )*
+                        }
+                    });
+                }
+                Fields::Named(fields) => {
+                    for named_field in fields {
+                        let attr =
+                            FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?;
+                        if !attr.skip {
+                            let name = &named_field.name;
+                            bindings.push(name.clone());
+                            values.push(quote! { #name });
+                        }
+                    }
+                    arms.push(quote! {
+                        Self::#var_name { #( #bindings, )* .. } => {
+                            1 #( + #crate_name::StrictEncodeSize::encoded_size(#values) )*
+                        }
+                    });
+                }
+            }
+        }
+
+        Ok(quote! {
+            #[allow(unused_variables)]
+            fn encoded_size(&self) -> usize {
+                match self {
+                    #( #arms ),*
+                }
+            }
+        })
+    }
+}
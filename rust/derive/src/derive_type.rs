@@ -30,8 +30,8 @@ use crate::params::{ContainerAttr, EnumAttr, FieldAttr, StrictDerive, VariantAtt
 
 struct DeriveType<'a>(&'a StrictDerive);
 struct DeriveProduct<'a>(&'a Fields);
-struct DeriveTuple<'a>(&'a Items<Field>);
-struct DeriveStruct<'a>(&'a Items<NamedField>);
+struct DeriveTuple<'a>(&'a Items<Field>, Option<u16>, bool);
+struct DeriveStruct<'a>(&'a Items<NamedField>, Option<u16>, bool);
 struct DeriveSum<'a>(&'a Items<Variant>, &'a ContainerAttr, EnumAttr);
 struct DeriveEnum<'a>(&'a Items<Variant>);
 struct DeriveUnion<'a>(&'a Items<Variant>);
@@ -41,6 +41,31 @@ impl StrictDerive {
         let trait_crate = &self.conf.strict_crate;
         let type_name = &self.data.name;
 
+        let impl_wire_format = match self.conf.wire_version {
+            Some(ref version) => self.derive_impl(quote! { #trait_crate::WireFormat }, quote! {
+                const WIRE_VERSION: u16 = #version;
+            }),
+            None => TokenStream2::new(),
+        };
+
+        if let DataInner::Union(fields) = &self.data.inner {
+            // Rust unions are encoded as a newtype over the active field
+            self.active_union_field(fields)?;
+            let impl_type = self
+                .derive_impl(quote! { #trait_crate::StrictType }, DeriveType(self).derive_type()?);
+            let impl_product =
+                self.derive_impl(quote! { #trait_crate::StrictProduct }, TokenStream2::new());
+            let impl_tuple = self.derive_impl(quote! { #trait_crate::StrictTuple }, quote! {
+                const FIELD_COUNT: u8 = 1;
+            });
+            return Ok(quote! {
+                #impl_type
+                #impl_product
+                #impl_tuple
+                #impl_wire_format
+            });
+        }
+
         let impl_type = self
             .data
             .derive(trait_crate, &ident!(StrictType), &DeriveType(self))?;
@@ -101,14 +126,20 @@ impl StrictDerive {
         };
 
         let impl_inner = match &self.data.inner {
-            DataInner::Struct(Fields::Named(fields)) => {
-                self.data
-                    .derive(trait_crate, &ident!(StrictStruct), &DeriveStruct(fields))?
-            }
-            DataInner::Struct(Fields::Unnamed(fields)) => {
-                self.data
-                    .derive(trait_crate, &ident!(StrictTuple), &DeriveTuple(fields))?
-            }
+            DataInner::Struct(Fields::Named(fields)) => self.data.derive(
+                trait_crate,
+                &ident!(StrictStruct),
+                &DeriveStruct(
+                    &self.ordered_fields(fields)?,
+                    self.schema_version()?,
+                    self.conf.schema_hash_check,
+                ),
+            )?,
+            DataInner::Struct(Fields::Unnamed(fields)) => self.data.derive(
+                trait_crate,
+                &ident!(StrictTuple),
+                &DeriveTuple(fields, self.schema_version()?, self.conf.schema_hash_check),
+            )?,
             DataInner::Enum(variants) if variants.enum_kind() == EnumKind::Primitive => {
                 self.data
                     .derive(trait_crate, &ident!(StrictEnum), &DeriveEnum(variants))?
@@ -124,6 +155,7 @@ impl StrictDerive {
             #impl_type
             #impl_outer
             #impl_inner
+            #impl_wire_format
         })
     }
 }
@@ -148,18 +180,104 @@ impl DeriveType<'_> {
             #strict_name
         })
     }
+
+    /// Generates code hashing schema of the named fields, which must be given
+    /// in their encoding order.
+    fn schema_fields(fields: &Items<NamedField>) -> Result<TokenStream2> {
+        let mut schema = Vec::with_capacity(fields.len());
+        for named_field in fields {
+            let attr = FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?;
+            if attr.skip {
+                continue;
+            }
+            if let Some(prefix_name) = attr.prefix_name(&named_field.name) {
+                let len = attr.prefix_len();
+                schema.push(quote! {
+                    hasher.write_str(#prefix_name);
+                    hasher.write_type::<[u8; #len]>();
+                });
+            }
+            let name = attr.field_name(&named_field.name);
+            let ty = &named_field.field.ty;
+            schema.push(quote! {
+                hasher.write_str(#name);
+                hasher.write_type::<#ty>();
+            });
+        }
+        Ok(quote! { #( #schema )* })
+    }
+
+    /// Generates code hashing schema of the unnamed fields.
+    fn schema_tuple(fields: &Items<Field>) -> Result<TokenStream2> {
+        let mut schema = Vec::with_capacity(fields.len());
+        for field in fields {
+            let attr = FieldAttr::with(field.attr.clone(), FieldKind::Unnamed)?;
+            if !attr.skip {
+                let ty = &field.ty;
+                schema.push(quote! { hasher.write_type::<#ty>(); });
+            }
+        }
+        Ok(quote! { #( #schema )* })
+    }
+
+    /// Generates `StrictType::strict_schema` hashing the type name followed by
+    /// the schema `inner` of the type fields or variants.
+    fn derive_schema(&self, inner: TokenStream2) -> Result<TokenStream2> {
+        let crate_name = &self.0.conf.strict_crate;
+        let version = self.0.schema_version()?.map(|_| {
+            quote! {
+                hasher.write_str("schemaVersion");
+                hasher.write_type::<u16>();
+            }
+        });
+        let constants = self.derive_type()?;
+        Ok(quote! {
+            #constants
+
+            fn strict_schema(
+                hasher: &mut #crate_name::SchemaHasher,
+                _: ::core::marker::PhantomData<Self>,
+            ) {
+                hasher.write_name::<Self>();
+                #version
+                #inner
+            }
+        })
+    }
 }
 
 impl DeriveInner for DeriveType<'_> {
     fn derive_unit_inner(&self) -> Result<TokenStream2> { self.derive_type() }
-    fn derive_struct_inner(&self, _fields: &Items<NamedField>) -> Result<TokenStream2> {
-        self.derive_type()
+    fn derive_struct_inner(&self, fields: &Items<NamedField>) -> Result<TokenStream2> {
+        let ordered = self.0.ordered_fields(fields)?;
+        self.derive_schema(DeriveType::schema_fields(&ordered)?)
     }
-    fn derive_tuple_inner(&self, _fields: &Items<Field>) -> Result<TokenStream2> {
-        self.derive_type()
+    fn derive_tuple_inner(&self, fields: &Items<Field>) -> Result<TokenStream2> {
+        self.derive_schema(DeriveType::schema_tuple(fields)?)
     }
-    fn derive_enum_inner(&self, _variants: &Items<Variant>) -> Result<TokenStream2> {
-        self.derive_type()
+    fn derive_enum_inner(&self, variants: &Items<Variant>) -> Result<TokenStream2> {
+        let crate_name = &self.0.conf.strict_crate;
+        let mut schema = Vec::with_capacity(variants.len());
+        for var in variants {
+            let attr = VariantAttr::try_from(var.attr.clone())?;
+            let name = attr.variant_name(&var.name);
+            let fields = match &var.fields {
+                Fields::Unit => TokenStream2::new(),
+                Fields::Named(fields) => DeriveType::schema_fields(fields)?,
+                Fields::Unnamed(fields) => DeriveType::schema_tuple(fields)?,
+            };
+            schema.push(quote! {
+                hasher.write_str(#name);
+                #fields
+            });
+        }
+        self.derive_schema(quote! {
+            for (tag, name) in <Self as #crate_name::StrictSum>::ALL_VARIANTS {
+                hasher.write_u64(*tag as u64);
+                hasher.write_str(name);
+            }
+            #( #schema )*
+        })
     }
 }
 
@@ -234,7 +352,7 @@ impl DeriveInner for DeriveTuple<'_> {
     }
 
     fn derive_tuple_inner(&self, fields: &Items<Field>) -> Result<TokenStream2> {
-        let field_count = fields.len();
+        let field_count = fields.len() + self.1.is_some() as usize + self.2 as usize;
         Ok(quote! {
             const FIELD_COUNT: u8 = #field_count as u8;
         })
@@ -248,11 +366,19 @@ impl DeriveInner for DeriveStruct<'_> {
     }
     fn derive_tuple_inner(&self, _fields: &Items<Field>) -> Result<TokenStream2> { unreachable!() }
 
-    fn derive_struct_inner(&self, fields: &Items<NamedField>) -> Result<TokenStream2> {
-        let mut name = Vec::with_capacity(fields.len());
-        for named_field in fields {
+    fn derive_struct_inner(&self, _fields: &Items<NamedField>) -> Result<TokenStream2> {
+        // Fields are taken from `self.0`, which holds them in the encoding order
+        let mut name = Vec::with_capacity(self.0.len() + 2);
+        if self.2 {
+            name.push(LitStr::new("schemaHash", Span::call_site()));
+        }
+        if self.1.is_some() {
+            name.push(LitStr::new("schemaVersion", Span::call_site()));
+        }
+        for named_field in self.0 {
             let attr = FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?;
             if !attr.skip {
+                name.extend(attr.prefix_name(&named_field.name));
                 name.push(attr.field_name(&named_field.name));
             }
         }
@@ -44,6 +44,175 @@
 //!
 //! Derivation macros accept `#[strict_encoding()]` attribute with the following
 //! arguments:
+//!
+//! # Arrays
+//!
+//! Arrays `[T; N]` of any length are encoded natively via const generics, so
+//! no `serde_big_array`-like workarounds are required. However, the standard
+//! library implements `Default` only for arrays up to 32 elements, and thus
+//! longer arrays do not implement `StrictDumb`. Mark such fields with
+//! `#[strict_type(big_array)]` to make the derived `StrictDumb` construct their
+//! dumb value element-wise.
+//!
+//! # Field order
+//!
+//! Structures with named fields are encoded in the field declaration order by
+//! default. A canonical order independent of the Rust source can be selected
+//! with `#[strict_type(field_order = ...)]` attribute:
+//! - `declaration` (default) keeps the declaration order;
+//! - `alphabetical` sorts fields by their Rust identifiers;
+//! - `explicit` uses positions provided with `#[strict_type(order = N)]` on
+//!   each of the non-skipped fields.
+//!
+//! The same order is used by the decoder and is reported in
+//! `StrictStruct::ALL_FIELDS`.
+//!
+//! # Count fields
+//!
+//! A collection field and a sibling integer field holding the number of its
+//! items can be linked with `#[strict_type(count_field = "n")]` attribute on
+//! the collection or `#[strict_type(counted_by = "items")]` on the count
+//! field; the count field must be encoded before the collection. The encoder
+//! fills the count from the collection length and writes the collection items
+//! without their own length prefix; the decoder reads the count and then
+//! exactly that many items. Encoding fails with `EncodeError::CountMismatch`
+//! (inside `io::Error` of `InvalidData` kind) if the count field was set to a
+//! non-default value different from the collection length.
+//!
+//! # Schema version
+//!
+//! `#[strict_type(schema_version = N)]` attribute on a structure prepends `N`
+//! encoded as `u16` to the structure data; for structures with named fields
+//! it is reported as `schemaVersion` field. Decoding fails with
+//! `DecodeError::IncompatibleSchemaVersion` if the version read differs from
+//! `N`.
+//!
+//! # Schema hash
+//!
+//! `#[strict_type(schema_hash_check)]` attribute on a structure prepends a
+//! 4-byte hash of the structure schema to the structure data, increasing its
+//! encoded size by 4 bytes; for structures with named fields it is reported as
+//! `schemaHash` field, which comes before `schemaVersion` field. The hash is
+//! computed with `strict_encoding::schema_hash` from the type names, field and
+//! variant names and, recursively, the schemata of the field types. Decoding
+//! fails with `DecodeError::SchemaHashMismatch` if the hash read differs from
+//! the hash of the current schema.
+//!
+//! # Field prefixes
+//!
+//! `#[strict_type(prefix = [0x01, 0x02])]` attribute on a named structure
+//! field writes the given constant bytes right before the field value; the
+//! bytes may be given as a byte string (`prefix = b"\x01\x02"`) as well. The
+//! bytes are reported as a separate `[u8; N]` field named after the original
+//! one with `Prefix` suffix (i.e. `dataPrefix` for `data` field), and the
+//! derivation fails if another field already has this name. Decoding fails with
+//! `DecodeError::InvalidFieldPrefix` if the bytes read differ.
+//!
+//! # Constant fields
+//!
+//! `#[strict_type(const_decode = EXPR)]` attribute on a named structure field
+//! makes encoder always write the value of `EXPR` in place of the field,
+//! ignoring the actual field value. Decoder checks that the value read equals
+//! `EXPR` and fails with `DecodeError::ConstantMismatch` otherwise; the field
+//! type must implement `PartialEq` and `Debug`.
+//!
+//! # Tagged unions
+//!
+//! Structures in which some fields are present only for specific values of
+//! another field can mark that field with
+//! `#[strict_type(union_discriminant_field = "tag")]` attribute at the type
+//! level and the dependent fields with `#[strict_type(when = "tag == 0x01")]`.
+//! The condition is a Rust expression given as a string literal, which may
+//! refer to the discriminant field by its Rust name. Fields
+//! whose condition is false are neither encoded nor decoded; the decoder sets
+//! them to `Default::default()`. The discriminant field must be encoded before
+//! all the conditional fields.
+//!
+//! Note that `StrictStruct::ALL_FIELDS` lists the conditional fields
+//! regardless of the discriminant value, i.e. it describes all the fields the
+//! structure may have and not the fields present in a specific encoding.
+//!
+//! # Zeroization
+//!
+//! `#[strict_type(zeroize_on_drop)]` attribute on a field holding secret data
+//! requires the field type to implement `zeroize::ZeroizeOnDrop`, for instance
+//! by wrapping the value into `zeroize::Zeroizing`, failing compilation
+//! otherwise. This is only a bound check: the derived decoder doesn't zeroize
+//! anything by itself, so the temporary buffers which the reader and the field
+//! type decoder may use while reading the value are not wiped. Requires
+//! `zeroize` feature of `strict_encoding`.
+//!
+//! # Hex literals
+//!
+//! Byte arrays can't be written in the attributes directly. Adding
+//! `#[strict_type(hex_literal)]` to a `[u8; N]` field makes its `dumb`,
+//! `const_decode` and `prefix` values to be read as hex strings, such that
+//! `#[strict_type(hex_literal, const_decode = "DEADBEEF")]` stands for the
+//! constant `[0xDE, 0xAD, 0xBE, 0xEF]`. The same constant can be written in
+//! short as `#[strict_type(hex_literal = "DEADBEEF")]`, which can't be combined
+//! with `const_decode`.
+//!
+//! # Single-direction types
+//!
+//! `#[strict_type(encode_only)]` attribute marks a type which must never be
+//! decoded (like a write-only log entry), and `#[strict_type(decode_only)]`
+//! marks a type which must never be encoded (like a superseded legacy format).
+//! Deriving `StrictDecode` for an `encode_only` type, or `StrictEncode` for a
+//! `decode_only` type fails compilation:
+//!
+//! ```
+//! # #[macro_use] extern crate strict_encoding_derive;
+//! #[derive(Clone, Default, StrictType, StrictEncode)]
+//! #[strict_type(lib = "Test", encode_only)]
+//! struct AuditEntry(u16);
+//! # fn main() {}
+//! ```
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate strict_encoding_derive;
+//! #[derive(Clone, Default, StrictType, StrictEncode, StrictDecode)]
+//! #[strict_type(lib = "Test", encode_only)]
+//! struct AuditEntry(u16);
+//! # fn main() {}
+//! ```
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate strict_encoding_derive;
+//! #[derive(Clone, Default, StrictType, StrictEncode, StrictDecode)]
+//! #[strict_type(lib = "Test", decode_only)]
+//! struct LegacyEntry(u16);
+//! # fn main() {}
+//! ```
+//!
+//! The two attributes can't be used together, even if neither of the
+//! directions is derived:
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate strict_encoding_derive;
+//! #[derive(Clone, Default, StrictType)]
+//! #[strict_type(lib = "Test", encode_only, decode_only)]
+//! struct Entry(u16);
+//! # fn main() {}
+//! ```
+//!
+//! # Hashing
+//!
+//! `#[strict_type(impl_hash)]` attribute makes `StrictEncode` derive also
+//! implement `Hash` by feeding the strict encoding of the value to the hasher,
+//! such that values with the same encoding always have the same hash. The
+//! generated implementation panics if the value can't be encoded.
+//!
+//! # Rust unions
+//!
+//! Rust `union` types are supported only when the active field is specified
+//! with `#[strict_type(union_field = "name")]` attribute. Such union is encoded
+//! as a newtype over its active field; the other fields are never read or
+//! written.
+//!
+//! The derived encoder reads the active field without any checks, so deriving
+//! `StrictEncode` or `StrictEncodeSize` additionally requires the union to
+//! implement `unsafe trait ActiveUnionField`, by which the user asserts that
+//! the active field is always the one which was initialized.
 
 #[macro_use]
 extern crate quote;
@@ -58,18 +227,54 @@ mod derive_dumb;
 mod derive_type;
 mod derive_encode;
 mod derive_decode;
+mod derive_size;
+mod derive_compat;
+mod strict_interface;
 
 use proc_macro::TokenStream;
-use syn::DeriveInput;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{DeriveInput, Ident};
 
 use crate::params::StrictDerive;
+use crate::strict_interface::InterfaceArgs;
+
+/// Name of the environment variable which, when set to `1`, makes derive
+/// macros print the code they generate to stderr.
+#[cfg(debug_assertions)]
+const ENV_EXPAND: &str = "STRICT_ENCODING_EXPAND";
+
+/// Prints generated token stream to stderr if [`ENV_EXPAND`] environment
+/// variable is set to `1`. With `pretty_expand` feature the output is
+/// pretty-printed when the tokens can be parsed as a Rust file, otherwise
+/// they are dumped as-is.
+///
+/// In release builds of the macro crate this is a no-op.
+#[cfg_attr(not(debug_assertions), allow(unused_variables))]
+fn dump_expansion(derive: &str, name: &Ident, tokens: TokenStream2) -> TokenStream2 {
+    #[cfg(debug_assertions)]
+    if std::env::var(ENV_EXPAND).as_deref() == Ok("1") {
+        #[cfg(feature = "pretty_expand")]
+        let code = syn::parse2::<syn::File>(tokens.clone())
+            .map(|file| prettyplease::unparse(&file))
+            .unwrap_or_else(|_| tokens.to_string());
+        #[cfg(not(feature = "pretty_expand"))]
+        let code = tokens.to_string();
+        eprintln!("// #[derive({derive})] for {name}\n{code}");
+    }
+    tokens
+}
 
 /// Derives [`StrictDumb`] implementation for the type.
 #[proc_macro_derive(StrictDumb, attributes(strict_type))]
 pub fn derive_strict_dumb(input: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
     StrictDerive::try_from(derive_input)
-        .and_then(|engine| engine.derive_dumb())
+        .and_then(|engine| {
+            let name = engine.data.name.clone();
+            engine
+                .derive_dumb()
+                .map(|ts| dump_expansion("StrictDumb", &name, ts))
+        })
         .unwrap_or_else(|e| e.to_compile_error())
         .into()
 }
@@ -79,7 +284,12 @@ pub fn derive_strict_dumb(input: TokenStream) -> TokenStream {
 pub fn derive_strict_type(input: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
     StrictDerive::try_from(derive_input)
-        .and_then(|engine| engine.derive_type())
+        .and_then(|engine| {
+            let name = engine.data.name.clone();
+            engine
+                .derive_type()
+                .map(|ts| dump_expansion("StrictType", &name, ts))
+        })
         .unwrap_or_else(|e| e.to_compile_error())
         .into()
 }
@@ -89,7 +299,12 @@ pub fn derive_strict_type(input: TokenStream) -> TokenStream {
 pub fn derive_strict_encode(input: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
     StrictDerive::try_from(derive_input)
-        .and_then(|engine| engine.derive_encode())
+        .and_then(|engine| {
+            let name = engine.data.name.clone();
+            engine
+                .derive_encode()
+                .map(|ts| dump_expansion("StrictEncode", &name, ts))
+        })
         .unwrap_or_else(|e| e.to_compile_error())
         .into()
 }
@@ -99,7 +314,88 @@ pub fn derive_strict_encode(input: TokenStream) -> TokenStream {
 pub fn derive_strict_decode(input: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
     StrictDerive::try_from(derive_input)
-        .and_then(|engine| engine.derive_decode())
+        .and_then(|engine| {
+            let name = engine.data.name.clone();
+            engine
+                .derive_decode()
+                .map(|ts| dump_expansion("StrictDecode", &name, ts))
+        })
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Derives [`StrictEncodeSize`] implementation for the type.
+#[proc_macro_derive(StrictEncodeSize, attributes(strict_type))]
+pub fn derive_strict_encode_size(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    StrictDerive::try_from(derive_input)
+        .and_then(|engine| {
+            let name = engine.data.name.clone();
+            engine
+                .derive_size()
+                .map(|ts| dump_expansion("StrictEncodeSize", &name, ts))
+        })
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Generates test module checking that the dumb value of the type (or of each
+/// of its enum variants) survives strict encoding round-trip and that its
+/// [`StrictEncodeSize`] matches the length of the encoded data.
+///
+/// The type must implement `StrictEncodeSize`, `StrictDecode`, `PartialEq`
+/// and `Debug`, and must be declared at a module level, since the generated
+/// tests refer to it via `super`.
+#[proc_macro_derive(StrictEncodeCompatTest, attributes(strict_type))]
+pub fn derive_strict_encode_compat_test(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    StrictDerive::try_from(derive_input)
+        .and_then(|engine| {
+            let name = engine.data.name.clone();
+            engine
+                .derive_compat_test()
+                .map(|ts| dump_expansion("StrictEncodeCompatTest", &name, ts))
+        })
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Checks that all public structures and enums of an inline module implement
+/// both [`StrictEncode`] and [`StrictDecode`], failing compilation otherwise.
+///
+/// Generic types are not checked, since they can't be used without specifying
+/// their type parameters. The path to the `strict_encoding` crate can be
+/// provided with `#[strict_interface(crate = path)]`.
+///
+/// ```
+/// # #[macro_use] extern crate strict_encoding_derive;
+/// #[strict_interface]
+/// mod protocol {
+///     #[derive(Clone, Default, StrictType, StrictEncode, StrictDecode)]
+///     #[strict_type(lib = "Test")]
+///     pub struct Ping(pub u8);
+/// }
+/// # fn main() {}
+/// ```
+///
+/// A public type which doesn't implement `StrictDecode` (or `StrictEncode`)
+/// fails the check:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate strict_encoding_derive;
+/// #[strict_interface]
+/// mod protocol {
+///     #[derive(Clone, Default, StrictType, StrictEncode)]
+///     #[strict_type(lib = "Test")]
+///     pub struct Ping(pub u8);
+/// }
+/// # fn main() {}
+/// ```
+#[proc_macro_attribute]
+pub fn strict_interface(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as InterfaceArgs);
+    let module = parse_macro_input!(item as syn::ItemMod);
+    strict_interface::strict_interface(args, module)
         .unwrap_or_else(|e| e.to_compile_error())
         .into()
 }
@@ -22,13 +22,13 @@
 use std::collections::HashMap;
 
 use amplify_syn::{
-    ArgValueReq, AttrReq, DataType, EnumKind, FieldKind, ListReq, ParametrizedAttr, TypeClass,
-    ValueClass,
+    ArgValueReq, AttrReq, DataInner, DataType, EnumKind, FieldKind, Fields, Items, ListReq,
+    NamedField, ParametrizedAttr, TypeClass, ValueClass,
 };
 use heck::ToLowerCamelCase;
-use proc_macro2::{Ident, Span};
+use proc_macro2::{Delimiter, Group, Ident, Span, TokenStream as TokenStream2, TokenTree};
 use quote::ToTokens;
-use syn::{DeriveInput, Error, Expr, LitInt, LitStr, Path, Result};
+use syn::{DeriveInput, Error, Expr, ExprLit, Lit, LitInt, LitStr, Path, Result, Type};
 
 const ATTR: &str = "strict_type";
 const ATTR_CRATE: &str = "crate";
@@ -46,6 +46,27 @@ const ATTR_TAG: &str = "tag";
 const ATTR_SKIP: &str = "skip";
 const ATTR_INTO_U8: &str = "into_u8";
 const ATTR_TRY_FROM_U8: &str = "try_from_u8";
+const ATTR_COUNT_FIELD: &str = "count_field";
+const ATTR_COUNTED_BY: &str = "counted_by";
+const ATTR_ZEROIZE_ON_DROP: &str = "zeroize_on_drop";
+const ATTR_BIG_ARRAY: &str = "big_array";
+const ATTR_HEX_LITERAL: &str = "hex_literal";
+const ATTR_UNION_FIELD: &str = "union_field";
+const ATTR_WIRE_VERSION: &str = "wire_version";
+const ATTR_SCHEMA_VERSION: &str = "schema_version";
+const ATTR_FIELD_ORDER: &str = "field_order";
+const ATTR_FIELD_ORDER_DECLARATION: &str = "declaration";
+const ATTR_FIELD_ORDER_ALPHABETICAL: &str = "alphabetical";
+const ATTR_FIELD_ORDER_EXPLICIT: &str = "explicit";
+const ATTR_ORDER: &str = "order";
+const ATTR_PREFIX: &str = "prefix";
+const ATTR_CONST_DECODE: &str = "const_decode";
+const ATTR_UNION_DISCRIMINANT_FIELD: &str = "union_discriminant_field";
+const ATTR_WHEN: &str = "when";
+const ATTR_IMPL_HASH: &str = "impl_hash";
+const ATTR_ENCODE_ONLY: &str = "encode_only";
+const ATTR_DECODE_ONLY: &str = "decode_only";
+const ATTR_SCHEMA_HASH_CHECK: &str = "schema_hash_check";
 
 pub struct ContainerAttr {
     pub strict_crate: Path,
@@ -54,6 +75,15 @@ pub struct ContainerAttr {
     pub dumb: Option<Expr>,
     pub encode_with: Option<Path>,
     pub decode_with: Option<Path>,
+    pub union_field: Option<LitStr>,
+    pub wire_version: Option<LitInt>,
+    pub field_order: FieldOrder,
+    pub schema_version: Option<LitInt>,
+    pub union_discriminant_field: Option<LitStr>,
+    pub impl_hash: bool,
+    pub encode_only: bool,
+    pub decode_only: bool,
+    pub schema_hash_check: bool,
 }
 
 pub struct EnumAttr {
@@ -66,6 +96,23 @@ pub struct FieldAttr {
     pub dumb: Option<Expr>,
     pub rename: Option<LitStr>,
     pub skip: bool,
+    pub count_field: Option<LitStr>,
+    pub counted_by: Option<LitStr>,
+    pub zeroize_on_drop: bool,
+    pub big_array: bool,
+    pub order: Option<LitInt>,
+    pub prefix: Option<Vec<u8>>,
+    pub const_decode: Option<Expr>,
+    pub when: Option<Expr>,
+}
+
+/// Link between a collection field and a sibling field holding the number of
+/// the collection items, which replaces the collection length prefix.
+pub struct CountLink {
+    pub count: Ident,
+    pub count_name: LitStr,
+    pub count_ty: Type,
+    pub items: Ident,
 }
 
 pub struct VariantAttr {
@@ -74,6 +121,14 @@ pub struct VariantAttr {
     pub tag: Option<LitInt>,
 }
 
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum FieldOrder {
+    #[default]
+    Declaration,
+    Alphabetical,
+    Explicit,
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum VariantTags {
     Repr,
@@ -90,6 +145,7 @@ impl ContainerAttr {
             (ATTR_DUMB, ArgValueReq::optional(ValueClass::Expr)),
             (ATTR_ENCODE_WITH, ArgValueReq::optional(TypeClass::Path)),
             (ATTR_DECODE_WITH, ArgValueReq::optional(TypeClass::Path)),
+            (ATTR_WIRE_VERSION, ArgValueReq::optional(ValueClass::int())),
         ]
     }
 }
@@ -97,9 +153,16 @@ impl ContainerAttr {
 impl EnumAttr {
     fn attr_req(map: HashMap<&str, ArgValueReq>, kind: EnumKind) -> AttrReq {
         let mut req = AttrReq::with(map);
+        let mut paths = vec![
+            path!(impl_hash),
+            path!(encode_only),
+            path!(decode_only),
+            path!(schema_hash_check),
+        ];
         if kind == EnumKind::Primitive {
-            req.path_req = ListReq::any_of(vec![path!(try_from_u8), path!(into_u8)], false);
+            paths.extend([path!(try_from_u8), path!(into_u8)]);
         }
+        req.path_req = ListReq::any_of(paths, false);
         req
     }
 }
@@ -109,11 +172,36 @@ impl TryFrom<ParametrizedAttr> for ContainerAttr {
 
     fn try_from(mut params: ParametrizedAttr) -> Result<Self> {
         let mut attrs = ContainerAttr::shared_attrs();
-        attrs.extend([(ATTR_TAGS, ArgValueReq::optional(TypeClass::Path))]);
+        attrs.extend([
+            (ATTR_TAGS, ArgValueReq::optional(TypeClass::Path)),
+            (ATTR_UNION_FIELD, ArgValueReq::optional(ValueClass::str())),
+            (ATTR_FIELD_ORDER, ArgValueReq::optional(TypeClass::Path)),
+            (ATTR_SCHEMA_VERSION, ArgValueReq::optional(ValueClass::int())),
+            (ATTR_UNION_DISCRIMINANT_FIELD, ArgValueReq::optional(ValueClass::str())),
+        ]);
         let map = HashMap::from_iter(attrs);
 
         params.check(EnumAttr::attr_req(map, EnumKind::Primitive))?;
 
+        let field_order = match params
+            .arg_value(ATTR_FIELD_ORDER)
+            .map(|path: Path| path.to_token_stream().to_string())
+            .as_deref()
+        {
+            Err(_) | Ok(ATTR_FIELD_ORDER_DECLARATION) => FieldOrder::Declaration,
+            Ok(ATTR_FIELD_ORDER_ALPHABETICAL) => FieldOrder::Alphabetical,
+            Ok(ATTR_FIELD_ORDER_EXPLICIT) => FieldOrder::Explicit,
+            Ok(unknown) => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    format!(
+                        "invalid value for `field_order` attribute `{unknown}`; only \
+                         `declaration`, `alphabetical` or `explicit` are allowed"
+                    ),
+                ));
+            }
+        };
+
         Ok(ContainerAttr {
             strict_crate: params
                 .arg_value(ATTR_CRATE)
@@ -129,6 +217,15 @@ impl TryFrom<ParametrizedAttr> for ContainerAttr {
                 .arg_value(ATTR_DECODE_WITH)
                 .or_else(|_| params.arg_value(ATTR_WITH))
                 .ok(),
+            union_field: params.arg_value(ATTR_UNION_FIELD).ok(),
+            wire_version: params.arg_value(ATTR_WIRE_VERSION).ok(),
+            field_order,
+            schema_version: params.arg_value(ATTR_SCHEMA_VERSION).ok(),
+            union_discriminant_field: params.arg_value(ATTR_UNION_DISCRIMINANT_FIELD).ok(),
+            impl_hash: params.has_verbatim(ATTR_IMPL_HASH),
+            encode_only: params.has_verbatim(ATTR_ENCODE_ONLY),
+            decode_only: params.has_verbatim(ATTR_DECODE_ONLY),
+            schema_hash_check: params.has_verbatim(ATTR_SCHEMA_HASH_CHECK),
         })
     }
 }
@@ -187,16 +284,76 @@ impl FieldAttr {
 
         if kind == FieldKind::Named {
             map.insert(ATTR_RENAME, ArgValueReq::optional(ValueClass::str()));
+            map.insert(ATTR_COUNT_FIELD, ArgValueReq::optional(ValueClass::str()));
+            map.insert(ATTR_COUNTED_BY, ArgValueReq::optional(ValueClass::str()));
+            map.insert(ATTR_ORDER, ArgValueReq::optional(ValueClass::int()));
+            map.insert(ATTR_PREFIX, ArgValueReq::optional(ValueClass::Expr));
+            map.insert(ATTR_CONST_DECODE, ArgValueReq::optional(ValueClass::Expr));
+            map.insert(ATTR_WHEN, ArgValueReq::optional(ValueClass::str()));
         }
 
+        // `hex_literal = "..."` argument is not known to the attribute checks,
+        // which would otherwise reject `hex_literal` used as a flag.
+        let hex_value = params
+            .args
+            .remove(ATTR_HEX_LITERAL)
+            .map(LitStr::try_from)
+            .transpose()?;
+
         let mut attr_req = AttrReq::with(map);
-        attr_req.path_req = ListReq::maybe_one(path!(skip));
+        attr_req.path_req = ListReq::any_of(
+            vec![path!(skip), path!(zeroize_on_drop), path!(big_array), path!(hex_literal)],
+            false,
+        );
         params.check(attr_req)?;
 
+        let mut dumb: Option<Expr> = params.arg_value(ATTR_DUMB).ok();
+        let mut const_decode: Option<Expr> = params.arg_value(ATTR_CONST_DECODE).ok();
+        let hex = params.has_verbatim(ATTR_HEX_LITERAL);
+        if hex {
+            for expr in dumb.iter_mut().chain(const_decode.iter_mut()) {
+                *expr = hex_literal(expr)?;
+            }
+        }
+        if let Some(lit) = hex_value {
+            if kind != FieldKind::Named {
+                return Err(Error::new(
+                    lit.span(),
+                    "`hex_literal` value can be used only with named fields",
+                ));
+            }
+            if const_decode.is_some() {
+                return Err(Error::new(
+                    lit.span(),
+                    "`hex_literal` value and `const_decode` attribute can't be used together",
+                ));
+            }
+            let bytes = hex_bytes(&lit)?;
+            const_decode = Some(parse_quote! { [#( #bytes ),*] });
+        }
+
+        let prefix = params
+            .arg_value(ATTR_PREFIX)
+            .ok()
+            .map(|expr: Expr| parse_prefix(&expr, hex))
+            .transpose()?;
+
         Ok(FieldAttr {
             rename: params.arg_value(ATTR_RENAME).ok(),
-            dumb: params.arg_value(ATTR_DUMB).ok(),
+            dumb,
             skip: params.has_verbatim(ATTR_SKIP),
+            count_field: params.arg_value(ATTR_COUNT_FIELD).ok(),
+            counted_by: params.arg_value(ATTR_COUNTED_BY).ok(),
+            zeroize_on_drop: params.has_verbatim(ATTR_ZEROIZE_ON_DROP),
+            big_array: params.has_verbatim(ATTR_BIG_ARRAY),
+            order: params.arg_value(ATTR_ORDER).ok(),
+            prefix,
+            const_decode,
+            when: params
+                .arg_value(ATTR_WHEN)
+                .ok()
+                .map(|when: LitStr| when.parse())
+                .transpose()?,
         })
     }
 
@@ -206,6 +363,211 @@ impl FieldAttr {
             Some(ref name) => name.clone(),
         }
     }
+
+    /// Returns name of the field holding the constant `prefix` bytes, if the
+    /// attribute is present.
+    pub fn prefix_name(&self, name: &Ident) -> Option<LitStr> {
+        self.prefix.as_ref().map(|_| {
+            let name = self.field_name(name);
+            LitStr::new(&format!("{}Prefix", name.value()), name.span())
+        })
+    }
+
+    /// Returns number of the `prefix` bytes, or zero if there is no prefix.
+    pub fn prefix_len(&self) -> usize { self.prefix.as_ref().map(Vec::len).unwrap_or_default() }
+
+    /// Returns expression constructing the `prefix` bytes as `[u8; N]` array.
+    pub fn prefix_bytes(&self) -> Option<TokenStream2> {
+        self.prefix.as_ref().map(|bytes| quote! { [#( #bytes ),*] })
+    }
+
+    /// Returns expression constructing the `const_decode` value of the field
+    /// type `ty`.
+    pub fn const_value(&self, ty: &Type) -> Option<TokenStream2> {
+        self.const_decode
+            .as_ref()
+            .map(|expr| quote! { { let value: #ty = #expr; value } })
+    }
+}
+
+/// Wraps `expr` encoding or decoding the field `name` of type `ty` into calls
+/// of the wire watching hooks.
+pub fn watch_field(
+    crate_name: &Path,
+    name: &LitStr,
+    ty: &Type,
+    expr: TokenStream2,
+) -> TokenStream2 {
+    quote! {{
+        #crate_name::wire_watch::field_start(#name, ::core::any::type_name::<#ty>());
+        let value = #expr;
+        #crate_name::wire_watch::field_end();
+        value
+    }}
+}
+
+/// Parses `prefix` attribute value, which is either a byte string or an array
+/// of integer literals; with `hex` set, a hex string literal is accepted as
+/// well.
+fn parse_prefix(expr: &Expr, hex: bool) -> Result<Vec<u8>> {
+    let bytes = match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::ByteStr(lit),
+            ..
+        }) => lit.value(),
+        Expr::Block(block) if block.block.stmts.len() == 1 => match &block.block.stmts[0] {
+            syn::Stmt::Expr(expr) => return parse_prefix(expr, hex),
+            _ => return Err(Error::new_spanned(expr, "invalid `prefix` attribute value")),
+        },
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) if hex => hex_bytes(lit)?,
+        Expr::Array(array) => array
+            .elems
+            .iter()
+            .map(|elem| match elem {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Int(int), ..
+                }) => int.base10_parse::<u8>(),
+                _ => Err(Error::new_spanned(elem, "`prefix` array must contain byte literals")),
+            })
+            .collect::<Result<_>>()?,
+        _ => {
+            return Err(Error::new_spanned(
+                expr,
+                "`prefix` attribute requires a byte string or an array of byte literals",
+            ))
+        }
+    };
+    if bytes.is_empty() {
+        return Err(Error::new_spanned(expr, "`prefix` attribute requires non-empty bytes"));
+    }
+    Ok(bytes)
+}
+
+/// Converts string literal with hex-encoded data into a byte array expression.
+fn hex_literal(expr: &Expr) -> Result<Expr> {
+    let lit = match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) => lit,
+        _ => {
+            return Err(Error::new_spanned(
+                expr,
+                "with `hex_literal` attribute the value must be a hex string literal",
+            ))
+        }
+    };
+    let bytes = hex_bytes(lit)?;
+    Ok(parse_quote! { [#( #bytes ),*] })
+}
+
+/// Parses bytes from a string literal with hex-encoded data.
+fn hex_bytes(lit: &LitStr) -> Result<Vec<u8>> {
+    let hex = lit.value();
+    if hex.len() % 2 != 0 {
+        return Err(Error::new(lit.span(), "hex literal must have an even number of digits"));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|pos| {
+            hex.get(pos..pos + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+        })
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| Error::new(lit.span(), "invalid hex literal"))
+}
+
+impl CountLink {
+    /// Local variable holding the count value computed by the encoder.
+    pub fn count_var(&self) -> Ident {
+        Ident::new(&format!("__{}_count", self.count), Span::call_site())
+    }
+
+    /// Collects links defined with `count_field` and `counted_by` attributes
+    /// on the structure fields, which must be given in their encoding order.
+    pub fn with(ordered: &Items<NamedField>) -> Result<Vec<CountLink>> {
+        let mut attrs = Vec::with_capacity(ordered.len());
+        for named_field in ordered {
+            let attr = FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?;
+            attrs.push((named_field, attr));
+        }
+
+        let lookup = |name: &LitStr| {
+            attrs
+                .iter()
+                .position(|(field, attr)| !attr.skip && field.name == name.value())
+                .ok_or_else(|| {
+                    Error::new(
+                        name.span(),
+                        format!("no non-skipped field named `{}` is present", name.value()),
+                    )
+                })
+        };
+
+        let mut links: Vec<CountLink> = vec![];
+        for (pos, (_, attr)) in attrs.iter().enumerate() {
+            let mut pairs = vec![];
+            if let Some(items) = &attr.counted_by {
+                pairs.push((pos, lookup(items)?, items));
+            }
+            if let Some(count) = &attr.count_field {
+                pairs.push((lookup(count)?, pos, count));
+            }
+            for (count, items, name) in pairs {
+                let (count_field, count_attr) = &attrs[count];
+                let (items_field, items_attr) = &attrs[items];
+                if count == items {
+                    return Err(Error::new(
+                        name.span(),
+                        "field can't be used as a count for itself",
+                    ));
+                }
+                if count > items {
+                    return Err(Error::new(
+                        name.span(),
+                        format!(
+                            "count field `{}` must be encoded before `{}` field",
+                            count_field.name, items_field.name
+                        ),
+                    ));
+                }
+                if [count_attr, items_attr]
+                    .iter()
+                    .any(|attr| attr.when.is_some() || attr.const_decode.is_some())
+                {
+                    return Err(Error::new(
+                        name.span(),
+                        "counted fields can't have `when` or `const_decode` attributes",
+                    ));
+                }
+                if links
+                    .iter()
+                    .any(|link| link.count == count_field.name && link.items == items_field.name)
+                {
+                    continue;
+                }
+                if links.iter().any(|link| {
+                    [&link.count, &link.items]
+                        .into_iter()
+                        .any(|linked| *linked == count_field.name || *linked == items_field.name)
+                }) {
+                    return Err(Error::new(
+                        name.span(),
+                        "a field can be linked to a single other field with `count_field` and \
+                         `counted_by` attributes",
+                    ));
+                }
+                links.push(CountLink {
+                    count: count_field.name.clone(),
+                    count_name: count_attr.field_name(&count_field.name),
+                    count_ty: count_field.field.ty.clone(),
+                    items: items_field.name.clone(),
+                });
+            }
+        }
+        Ok(links)
+    }
 }
 
 impl TryFrom<ParametrizedAttr> for VariantAttr {
@@ -246,10 +608,371 @@ pub struct StrictDerive {
 impl TryFrom<DeriveInput> for StrictDerive {
     type Error = Error;
 
-    fn try_from(input: DeriveInput) -> Result<Self> {
+    fn try_from(mut input: DeriveInput) -> Result<Self> {
+        wrap_array_args(&mut input);
         let params = ParametrizedAttr::with(ATTR, &input.attrs)?;
         let conf = ContainerAttr::try_from(params)?;
         let data = DataType::with(input, ident!(strict_type))?;
+        if let DataInner::Enum(variants) = &data.inner {
+            for named_field in variants.iter().flat_map(|var| match &var.fields {
+                Fields::Named(fields) => fields.iter().collect(),
+                _ => Vec::new(),
+            }) {
+                let attr = FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?;
+                let name = if attr.prefix.is_some() {
+                    ATTR_PREFIX
+                } else if attr.const_decode.is_some() {
+                    ATTR_CONST_DECODE
+                } else if attr.when.is_some() {
+                    ATTR_WHEN
+                } else if attr.count_field.is_some() {
+                    ATTR_COUNT_FIELD
+                } else if attr.counted_by.is_some() {
+                    ATTR_COUNTED_BY
+                } else {
+                    continue;
+                };
+                return Err(Error::new(
+                    named_field.name.span(),
+                    format!("`{name}` attribute can be used only with structure fields"),
+                ));
+            }
+        }
+        if conf.union_field.is_some() && !matches!(data.inner, DataInner::Union(_)) {
+            return Err(Error::new(
+                Span::call_site(),
+                "`union_field` attribute can be used only with Rust unions",
+            ));
+        }
+        if conf.field_order != FieldOrder::Declaration &&
+            !matches!(data.inner, DataInner::Struct(Fields::Named(_)))
+        {
+            return Err(Error::new(
+                Span::call_site(),
+                "`field_order` attribute can be used only with structures having named fields",
+            ));
+        }
+        if conf.encode_only && conf.decode_only {
+            return Err(Error::new(
+                Span::call_site(),
+                "`encode_only` and `decode_only` attributes can't be used together",
+            ));
+        }
+        if conf.union_discriminant_field.is_some() &&
+            !matches!(data.inner, DataInner::Struct(Fields::Named(_)))
+        {
+            return Err(Error::new(
+                Span::call_site(),
+                "`union_discriminant_field` attribute can be used only with structures having \
+                 named fields",
+            ));
+        }
+        if conf.schema_version.is_some() && !matches!(data.inner, DataInner::Struct(_)) {
+            return Err(Error::new(
+                Span::call_site(),
+                "`schema_version` attribute can be used only with structures",
+            ));
+        }
+        if conf.schema_hash_check && !matches!(data.inner, DataInner::Struct(_)) {
+            return Err(Error::new(
+                Span::call_site(),
+                "`schema_hash_check` attribute can be used only with structures",
+            ));
+        }
+        if let DataInner::Struct(Fields::Named(fields)) = &data.inner {
+            check_field_names(&conf, fields)?;
+        }
         Ok(Self { data, conf })
     }
 }
+
+/// Wraps array values of `prefix` arguments in the field attributes into block
+/// expressions, since `amplify_syn` can't parse array expressions as attribute
+/// argument values.
+fn wrap_array_args(input: &mut DeriveInput) {
+    fn wrap(tokens: TokenStream2) -> TokenStream2 {
+        let mut wrapped = Vec::new();
+        for tree in tokens {
+            let tree = match tree {
+                TokenTree::Group(group)
+                    if group.delimiter() == Delimiter::Bracket &&
+                        matches!(
+                            wrapped.as_slice(),
+                            [.., TokenTree::Ident(name), TokenTree::Punct(eq)]
+                                if name == ATTR_PREFIX && eq.as_char() == '='
+                        ) =>
+                {
+                    let span = group.span();
+                    let mut block = Group::new(Delimiter::Brace, TokenTree::Group(group).into());
+                    block.set_span(span);
+                    TokenTree::Group(block)
+                }
+                TokenTree::Group(group) => {
+                    let mut inner = Group::new(group.delimiter(), wrap(group.stream()));
+                    inner.set_span(group.span());
+                    TokenTree::Group(inner)
+                }
+                tree => tree,
+            };
+            wrapped.push(tree);
+        }
+        wrapped.into_iter().collect()
+    }
+
+    let fields: Vec<&mut syn::Field> = match &mut input.data {
+        syn::Data::Struct(data) => data.fields.iter_mut().collect(),
+        syn::Data::Enum(data) => data
+            .variants
+            .iter_mut()
+            .flat_map(|variant| variant.fields.iter_mut())
+            .collect(),
+        syn::Data::Union(data) => data.fields.named.iter_mut().collect(),
+    };
+    for attr in fields
+        .into_iter()
+        .flat_map(|field| field.attrs.iter_mut())
+        .filter(|attr| attr.path.is_ident(ATTR))
+    {
+        attr.tokens = wrap(attr.tokens.clone());
+    }
+}
+
+/// Checks that the structure fields, the fields added by `prefix` attributes,
+/// the `schemaVersion` field added by `schema_version` attribute and the
+/// `schemaHash` field added by `schema_hash_check` attribute all have distinct
+/// names.
+fn check_field_names(conf: &ContainerAttr, fields: &Items<NamedField>) -> Result<()> {
+    let mut names: Vec<(String, &Ident)> = vec![];
+    for named_field in fields {
+        let attr = FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?;
+        if attr.skip {
+            continue;
+        }
+        let field_names =
+            [Some(attr.field_name(&named_field.name)), attr.prefix_name(&named_field.name)];
+        for name in field_names.into_iter().flatten() {
+            if let (Some(version), "schemaVersion") = (&conf.schema_version, name.value().as_str())
+            {
+                let mut err = Error::new(
+                    name.span(),
+                    format!(
+                        "field `{}` conflicts with `schemaVersion` field added by \
+                         `schema_version` attribute",
+                        named_field.name
+                    ),
+                );
+                err.combine(Error::new(version.span(), "schema version is defined here"));
+                return Err(err);
+            }
+            if conf.schema_hash_check && name.value() == "schemaHash" {
+                return Err(Error::new(
+                    name.span(),
+                    format!(
+                        "field `{}` conflicts with `schemaHash` field added by \
+                         `schema_hash_check` attribute",
+                        named_field.name
+                    ),
+                ));
+            }
+            if let Some((_, other)) = names.iter().find(|(other, _)| *other == name.value()) {
+                return Err(Error::new(
+                    name.span(),
+                    format!(
+                        "field `{}` conflicts with field `{other}`, both using `{}` name",
+                        named_field.name,
+                        name.value()
+                    ),
+                ));
+            }
+            names.push((name.value(), &named_field.name));
+        }
+    }
+    Ok(())
+}
+
+impl StrictDerive {
+    /// Returns field of a Rust `union` which is designated as active by the
+    /// `union_field` attribute.
+    pub fn active_union_field<'a>(&self, fields: &'a Items<NamedField>) -> Result<&'a NamedField> {
+        let name = self.conf.union_field.as_ref().ok_or_else(|| {
+            Error::new(
+                Span::call_site(),
+                "strict encoding of a Rust union requires `union_field` attribute specifying \
+                 which of the union fields is active",
+            )
+        })?;
+        fields
+            .iter()
+            .find(|field| field.name == name.value())
+            .ok_or_else(|| {
+                Error::new(name.span(), format!("union has no field named `{}`", name.value()))
+            })
+    }
+
+    /// Generates compile-time check that the union implements
+    /// `ActiveUnionField`, i.e. the user asserted that the field specified
+    /// with `union_field` attribute is always initialized.
+    pub fn active_union_check(&self) -> TokenStream2 {
+        let crate_name = &self.conf.strict_crate;
+        quote! {
+            fn assert_active_union_field<T: #crate_name::ActiveUnionField>() {}
+            assert_active_union_field::<Self>();
+        }
+    }
+
+    /// Returns structure fields in the order they must be encoded, as defined
+    /// by the `field_order` attribute.
+    pub fn ordered_fields(&self, fields: &Items<NamedField>) -> Result<Items<NamedField>> {
+        let mut positions = Vec::with_capacity(fields.len());
+        for named_field in fields {
+            let attr = FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?;
+            match (self.conf.field_order, attr.order) {
+                (FieldOrder::Explicit, Some(order)) => {
+                    let pos = order.base10_parse::<u16>()?;
+                    if positions.contains(&pos) {
+                        return Err(Error::new(
+                            order.span(),
+                            format!("more than one field has `order = {pos}`"),
+                        ));
+                    }
+                    positions.push(pos);
+                }
+                (FieldOrder::Explicit, None) if attr.skip => positions.push(u16::MAX),
+                (FieldOrder::Explicit, None) => {
+                    return Err(Error::new(
+                        named_field.name.span(),
+                        "with `field_order = explicit` each field must specify its position with \
+                         `order` attribute",
+                    ));
+                }
+                (_, Some(order)) => {
+                    return Err(Error::new(
+                        order.span(),
+                        "`order` attribute requires `field_order = explicit` on the type",
+                    ));
+                }
+                (_, None) => {}
+            }
+        }
+
+        let mut ordered = fields.clone();
+        match self.conf.field_order {
+            FieldOrder::Declaration => {}
+            FieldOrder::Alphabetical => {
+                ordered.sort_by_key(|named_field| named_field.name.to_string())
+            }
+            FieldOrder::Explicit => {
+                let mut keyed = positions.into_iter().zip(fields).collect::<Vec<_>>();
+                keyed.sort_by_key(|(pos, _)| *pos);
+                for (field, (_, named_field)) in ordered.iter_mut().zip(keyed) {
+                    *field = named_field.clone();
+                }
+            }
+        }
+        Ok(ordered)
+    }
+
+    /// Returns the field named by the `union_discriminant_field` attribute,
+    /// checking that it precedes in the `ordered` fields all the fields with
+    /// `when` attribute.
+    pub fn discriminant_field(&self, ordered: &Items<NamedField>) -> Result<Option<Ident>> {
+        let name = self.conf.union_discriminant_field.as_ref();
+        let mut discriminant = None;
+        for named_field in ordered {
+            let attr = FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?;
+            if name.map(LitStr::value).as_deref() == Some(&named_field.name.to_string()) {
+                if attr.skip || attr.when.is_some() {
+                    return Err(Error::new(
+                        named_field.name.span(),
+                        "discriminant field can't be skipped or have `when` attribute",
+                    ));
+                }
+                discriminant = Some(named_field.name.clone());
+                continue;
+            }
+            let Some(when) = &attr.when else {
+                continue;
+            };
+            match name {
+                None => {
+                    return Err(Error::new_spanned(
+                        when,
+                        "`when` attribute requires `union_discriminant_field` on the type",
+                    ));
+                }
+                Some(name) if discriminant.is_none() => {
+                    return Err(Error::new_spanned(
+                        when,
+                        format!(
+                            "field with `when` attribute must be encoded after `{}` discriminant \
+                             field",
+                            name.value()
+                        ),
+                    ));
+                }
+                Some(_) => {}
+            }
+        }
+        match (name, discriminant) {
+            (Some(name), None) => Err(Error::new(
+                name.span(),
+                format!("structure has no field named `{}`", name.value()),
+            )),
+            (_, discriminant) => Ok(discriminant),
+        }
+    }
+
+    /// Returns code binding a copy of the discriminant field value to a local
+    /// variable with the field name, such that `when` conditions can refer to
+    /// it.
+    pub fn discriminant_binding(&self, ordered: &Items<NamedField>) -> Result<TokenStream2> {
+        Ok(self
+            .discriminant_field(ordered)?
+            .map(|name| {
+                quote! {
+                    #[allow(unused_variables)]
+                    let #name = ::core::clone::Clone::clone(&self.#name);
+                }
+            })
+            .unwrap_or_default())
+    }
+
+    /// Fails if the type is restricted to a single direction with
+    /// `encode_only` or `decode_only` attribute, and `derive` name is a derive
+    /// macro for the other direction.
+    pub fn check_direction(&self, derive: &str) -> Result<()> {
+        let attr = match derive {
+            "StrictDecode" if self.conf.encode_only => ATTR_ENCODE_ONLY,
+            "StrictEncode" if self.conf.decode_only => ATTR_DECODE_ONLY,
+            "StrictEncodeCompatTest" if self.conf.encode_only => ATTR_ENCODE_ONLY,
+            "StrictEncodeCompatTest" if self.conf.decode_only => ATTR_DECODE_ONLY,
+            _ => return Ok(()),
+        };
+        Err(Error::new(
+            Span::call_site(),
+            format!("{derive} can't be derived for a type marked with `{attr}` attribute"),
+        ))
+    }
+
+    /// Returns schema version defined with the `schema_version` attribute.
+    pub fn schema_version(&self) -> Result<Option<u16>> {
+        self.conf
+            .schema_version
+            .as_ref()
+            .map(LitInt::base10_parse)
+            .transpose()
+    }
+
+    /// Generates trait implementation with the provided body. Unlike
+    /// [`DataType::derive`], works for Rust unions.
+    pub fn derive_impl(&self, trait_name: TokenStream2, inner: TokenStream2) -> TokenStream2 {
+        let (impl_generics, ty_generics, where_clause) = self.data.generics.split_for_impl();
+        let ident_name = &self.data.name;
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #trait_name for #ident_name #ty_generics #where_clause {
+                #inner
+            }
+        }
+    }
+}
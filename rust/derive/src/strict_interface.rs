@@ -0,0 +1,79 @@
+// Derivation macro library for strict encoding.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::parse::{Parse, ParseStream};
+use syn::{Error, Item, ItemMod, Path, Result, Visibility};
+
+/// Arguments of `#[strict_interface]` attribute.
+pub struct InterfaceArgs {
+    strict_crate: Path,
+}
+
+impl Parse for InterfaceArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.is_empty() {
+            return Ok(InterfaceArgs {
+                strict_crate: path!(strict_encoding),
+            });
+        }
+        input.parse::<Token![crate]>()?;
+        input.parse::<Token![=]>()?;
+        let strict_crate = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(InterfaceArgs { strict_crate })
+    }
+}
+
+/// Adds compile-time checks that all public non-generic structures and enums
+/// declared in the module implement both `StrictEncode` and `StrictDecode`.
+pub fn strict_interface(args: InterfaceArgs, mut module: ItemMod) -> Result<TokenStream2> {
+    let crate_name = &args.strict_crate;
+    let Some((_, items)) = &mut module.content else {
+        return Err(Error::new(
+            Span::call_site(),
+            "`strict_interface` attribute can be applied only to inline modules",
+        ));
+    };
+
+    let checks = items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Struct(item) => Some((&item.vis, &item.ident, &item.generics)),
+            Item::Enum(item) => Some((&item.vis, &item.ident, &item.generics)),
+            _ => None,
+        })
+        .filter(|(vis, _, generics)| {
+            matches!(vis, Visibility::Public(_)) && generics.params.is_empty()
+        })
+        .map(|(_, ident, _)| {
+            quote_spanned! { ident.span() =>
+                const _: fn() = || {
+                    fn assert_strict_io<T: #crate_name::StrictEncode + #crate_name::StrictDecode>() {}
+                    assert_strict_io::<#ident>();
+                };
+            }
+        })
+        .collect::<Vec<_>>();
+    items.extend(checks.into_iter().map(Item::Verbatim));
+
+    Ok(quote! { #module })
+}
@@ -213,3 +213,689 @@ fn enum_custom_tags() -> common::Result {
 
     Ok(())
 }
+
+#[test]
+fn struct_count_field() -> common::Result {
+    use amplify::confinement::TinyVec;
+    use strict_encoding::{
+        DecodeError, DeserializeError, EncodeError, StrictDeserialize, StrictEncodeSize,
+        StrictWriter,
+    };
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode, StrictEncodeSize)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Counted {
+        #[strict_type(counted_by = "items")]
+        n: u32,
+        #[strict_type(count_field = "n")]
+        items: TinyVec<u16>,
+    }
+
+    impl StrictSerialize for Counted {}
+    impl StrictDeserialize for Counted {}
+
+    let counted = Counted {
+        n: 2,
+        items: tiny_vec![1, 2],
+    };
+    let data = counted.to_strict_serialized::<256>().unwrap();
+    assert_eq!(data.as_slice(), &[2, 0, 0, 0, 1, 0, 2, 0]);
+    assert_eq!(counted.encoded_size(), data.len());
+    assert_eq!(Counted::from_strict_serialized::<256>(data).unwrap(), counted);
+
+    let unset = Counted {
+        n: 0,
+        items: tiny_vec![1, 2],
+    };
+    let data = unset.to_strict_serialized::<256>().unwrap();
+    assert_eq!(data.as_slice(), &[2, 0, 0, 0, 1, 0, 2, 0]);
+    assert_eq!(Counted::from_strict_serialized::<256>(data).unwrap(), counted);
+
+    let wrong = Counted {
+        n: 3,
+        items: tiny_vec![1, 2],
+    };
+    let err = wrong
+        .strict_encode(StrictWriter::in_memory(256))
+        .unwrap_err();
+    assert_eq!(
+        err.get_ref()
+            .and_then(|err| err.downcast_ref::<EncodeError>()),
+        Some(&EncodeError::CountMismatch {
+            field: s!("n"),
+            count: s!("3"),
+            len: 2
+        })
+    );
+
+    let data = confined_vec![3u8, 0, 0, 0, 1, 0, 2, 0];
+    assert!(Counted::from_strict_serialized::<256>(data).is_err());
+
+    let data = confined_vec![0u8, 1, 0, 0];
+    assert!(matches!(
+        Counted::from_strict_serialized::<256>(data),
+        Err(DeserializeError::Decode(DecodeError::Confinement(_)))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn field_zeroize_on_drop() -> common::Result {
+    use strict_encoding::zeroize::Zeroizing;
+    use strict_encoding::StrictDeserialize;
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Keys {
+        id: u8,
+        #[strict_type(zeroize_on_drop)]
+        secret: Zeroizing<[u8; 4]>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Secret(#[strict_type(zeroize_on_drop)] Zeroizing<[u8; 4]>);
+
+    impl StrictSerialize for Keys {}
+    impl StrictDeserialize for Keys {}
+    impl StrictSerialize for Secret {}
+    impl StrictDeserialize for Secret {}
+
+    let keys = Keys {
+        id: 1,
+        secret: Zeroizing::new([0xCA, 0xFE, 0xBA, 0xBE]),
+    };
+    let data = keys.to_strict_serialized::<256>().unwrap();
+    assert_eq!(data.as_slice(), &[1, 0xCA, 0xFE, 0xBA, 0xBE]);
+    assert_eq!(Keys::from_strict_serialized::<256>(data).unwrap(), keys);
+
+    let secret = Secret(Zeroizing::new([0xCA, 0xFE, 0xBA, 0xBE]));
+    let data = secret.to_strict_serialized::<256>().unwrap();
+    assert_eq!(Secret::from_strict_serialized::<256>(data).unwrap(), secret);
+
+    Ok(())
+}
+
+#[test]
+fn rust_union() -> common::Result {
+    use strict_encoding::{ActiveUnionField, StrictDeserialize};
+
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, union_field = "int")]
+    union Raw {
+        int: u32,
+        bytes: [u8; 4],
+    }
+
+    // SAFETY: `Raw` is always constructed with `int` field
+    unsafe impl ActiveUnionField for Raw {}
+    impl StrictSerialize for Raw {}
+    impl StrictDeserialize for Raw {}
+
+    let raw = Raw { int: 0xCAFE_BABE };
+    let data = raw.to_strict_serialized::<256>().unwrap();
+    assert_eq!(data.as_slice(), &[0xBE, 0xBA, 0xFE, 0xCA]);
+    let raw = Raw::from_strict_serialized::<256>(data).unwrap();
+    assert_eq!(unsafe { raw.int }, 0xCAFE_BABE);
+    assert_eq!(unsafe { Raw::strict_dumb().bytes }, [0u8; 4]);
+
+    Ok(())
+}
+
+#[test]
+fn big_array() -> common::Result {
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Signature {
+        #[strict_type(big_array)]
+        bytes: [u8; 64],
+        #[strict_type(big_array)]
+        extra: [u16; 33],
+    }
+
+    impl StrictSerialize for Signature {}
+
+    let sig = Signature {
+        bytes: [0xAB; 64],
+        extra: [0; 33],
+    };
+    let data = sig.to_strict_serialized::<256>().unwrap();
+    assert_eq!(data.len(), 64 + 66);
+    assert_eq!(&data[..64], &[0xAB; 64]);
+
+    let dumb = Signature::strict_dumb();
+    assert_eq!(dumb.bytes, [0u8; 64]);
+    assert_eq!(dumb.extra, [0u16; 33]);
+
+    Ok(())
+}
+
+#[test]
+fn struct_field_order() -> common::Result {
+    use strict_encoding::{StrictDeserialize, StrictStruct};
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, field_order = alphabetical)]
+    struct Alphabetical {
+        zeta: u8,
+        beta: u16,
+        alpha: u8,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, field_order = explicit)]
+    struct Explicit {
+        #[strict_type(order = 2)]
+        zeta: u8,
+        #[strict_type(skip)]
+        gamma: u8,
+        #[strict_type(order = 0)]
+        beta: u16,
+        #[strict_type(order = 1)]
+        alpha: u8,
+    }
+
+    impl StrictSerialize for Alphabetical {}
+    impl StrictDeserialize for Alphabetical {}
+    impl StrictSerialize for Explicit {}
+    impl StrictDeserialize for Explicit {}
+
+    assert_eq!(Alphabetical::ALL_FIELDS, &["alpha", "beta", "zeta"]);
+    assert_eq!(Explicit::ALL_FIELDS, &["beta", "alpha", "zeta"]);
+
+    let val = Alphabetical {
+        zeta: 3,
+        beta: 2,
+        alpha: 1,
+    };
+    let data = val.to_strict_serialized::<16>().unwrap();
+    assert_eq!(data.as_slice(), &[1, 2, 0, 3]);
+    assert_eq!(Alphabetical::from_strict_serialized::<16>(data).unwrap(), val);
+
+    let val = Explicit {
+        zeta: 3,
+        gamma: 0,
+        beta: 2,
+        alpha: 1,
+    };
+    let data = val.to_strict_serialized::<16>().unwrap();
+    assert_eq!(data.as_slice(), &[2, 0, 1, 3]);
+    assert_eq!(Explicit::from_strict_serialized::<16>(data).unwrap(), val);
+
+    Ok(())
+}
+
+#[test]
+fn struct_schema_version() -> common::Result {
+    use strict_encoding::{
+        DecodeError, DeserializeError, StrictDeserialize, StrictStruct, StrictTuple,
+    };
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, schema_version = 3)]
+    struct Named {
+        value: u8,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, schema_version = 0x0102)]
+    struct Tuple(u8);
+
+    impl StrictSerialize for Named {}
+    impl StrictDeserialize for Named {}
+    impl StrictSerialize for Tuple {}
+    impl StrictDeserialize for Tuple {}
+
+    assert_eq!(Named::ALL_FIELDS, &["schemaVersion", "value"]);
+    assert_eq!(Tuple::FIELD_COUNT, 2);
+
+    let data = Named { value: 0xA5 }.to_strict_serialized::<16>().unwrap();
+    assert_eq!(data.as_slice(), &[3, 0, 0xA5]);
+    assert_eq!(Named::from_strict_serialized::<16>(data).unwrap(), Named { value: 0xA5 });
+
+    let data = Tuple(0xA5).to_strict_serialized::<16>().unwrap();
+    assert_eq!(data.as_slice(), &[2, 1, 0xA5]);
+    assert_eq!(Tuple::from_strict_serialized::<16>(data).unwrap(), Tuple(0xA5));
+
+    let data = confined_vec![4, 0, 0xA5];
+    assert_eq!(
+        Named::from_strict_serialized::<16>(data),
+        Err(DeserializeError::Decode(DecodeError::IncompatibleSchemaVersion {
+            expected: 3,
+            found: 4
+        }))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn struct_schema_hash_check() -> common::Result {
+    use strict_encoding::{
+        schema_hash, DecodeError, DeserializeError, StrictDeserialize, StrictEncodeSize,
+        StrictStruct, StrictTuple,
+    };
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode, StrictEncodeSize)]
+    #[strict_type(lib = TEST_LIB, rename = "Inner")]
+    struct InnerV1 {
+        value: u8,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, rename = "Inner")]
+    struct InnerV2 {
+        value: u16,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode, StrictEncodeSize)]
+    #[strict_type(lib = TEST_LIB, rename = "Record", schema_hash_check)]
+    struct RecordV1 {
+        inner: InnerV1,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, rename = "Record", schema_hash_check)]
+    struct RecordV2 {
+        inner: InnerV2,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, schema_hash_check)]
+    struct Tuple(u8);
+
+    impl StrictSerialize for RecordV1 {}
+    impl StrictDeserialize for RecordV1 {}
+    impl StrictDeserialize for RecordV2 {}
+    impl StrictSerialize for Tuple {}
+    impl StrictDeserialize for Tuple {}
+
+    assert_eq!(RecordV1::ALL_FIELDS, &["schemaHash", "inner"]);
+    assert_eq!(Tuple::FIELD_COUNT, 2);
+    assert_ne!(schema_hash::<RecordV1>(), schema_hash::<RecordV2>());
+
+    let record = RecordV1 {
+        inner: InnerV1 { value: 0xA5 },
+    };
+    let data = record.to_strict_serialized::<16>().unwrap();
+    assert_eq!(&data[..4], &schema_hash::<RecordV1>());
+    assert_eq!(&data[4..], &[0xA5]);
+    assert_eq!(record.encoded_size(), data.len());
+    assert_eq!(RecordV1::from_strict_serialized::<16>(data.clone()).unwrap(), record);
+    assert_eq!(
+        RecordV2::from_strict_serialized::<16>(data),
+        Err(DeserializeError::Decode(DecodeError::SchemaHashMismatch {
+            expected: schema_hash::<RecordV2>(),
+            found: schema_hash::<RecordV1>(),
+        }))
+    );
+
+    let data = Tuple(0xA5).to_strict_serialized::<16>().unwrap();
+    assert_eq!(&data[..4], &schema_hash::<Tuple>());
+    assert_eq!(Tuple::from_strict_serialized::<16>(data).unwrap(), Tuple(0xA5));
+
+    Ok(())
+}
+
+#[test]
+fn struct_field_prefix() -> common::Result {
+    use strict_encoding::{DecodeError, DeserializeError, StrictDeserialize, StrictStruct};
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Framed {
+        #[strict_type(prefix = b"\xCA\xFE")]
+        data: u16,
+        #[strict_type(prefix = [0x0A])]
+        tail: u8,
+    }
+
+    impl StrictSerialize for Framed {}
+    impl StrictDeserialize for Framed {}
+
+    assert_eq!(Framed::ALL_FIELDS, &["dataPrefix", "data", "tailPrefix", "tail"]);
+
+    let framed = Framed {
+        data: 0x0201,
+        tail: 3,
+    };
+    let data = framed.to_strict_serialized::<16>().unwrap();
+    assert_eq!(data.as_slice(), &[0xCA, 0xFE, 1, 2, 0x0A, 3]);
+    assert_eq!(Framed::from_strict_serialized::<16>(data).unwrap(), framed);
+
+    let data = confined_vec![0xCA, 0xFF, 1, 2, 0x0A, 3];
+    assert_eq!(
+        Framed::from_strict_serialized::<16>(data),
+        Err(DeserializeError::Decode(DecodeError::InvalidFieldPrefix {
+            field: s!("data"),
+            expected: vec![0xCA, 0xFE],
+            got: vec![0xCA, 0xFF],
+        }))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn struct_const_decode() -> common::Result {
+    use strict_encoding::{DecodeError, DeserializeError, StrictDeserialize, StrictEncodeSize};
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode, StrictEncodeSize)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Versioned {
+        #[strict_type(const_decode = 0x01u8)]
+        version: u8,
+        data: u16,
+    }
+
+    impl StrictSerialize for Versioned {}
+    impl StrictDeserialize for Versioned {}
+
+    let value = Versioned {
+        version: 7,
+        data: 0x0302,
+    };
+    assert_eq!(value.encoded_size(), 3);
+    let data = value.to_strict_serialized::<16>().unwrap();
+    assert_eq!(data.as_slice(), &[1, 2, 3]);
+    assert_eq!(Versioned::from_strict_serialized::<16>(data).unwrap(), Versioned {
+        version: 1,
+        data: 0x0302,
+    });
+
+    let data = confined_vec![2, 2, 3];
+    assert_eq!(
+        Versioned::from_strict_serialized::<16>(data),
+        Err(DeserializeError::Decode(DecodeError::ConstantMismatch {
+            field: s!("version"),
+            expected: s!("1"),
+            got: s!("2"),
+        }))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn tagged_union_struct() -> common::Result {
+    use amplify::confinement::SmallBlob;
+    use strict_encoding::{StrictDeserialize, StrictEncodeSize};
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode, StrictEncodeSize)]
+    #[strict_type(lib = TEST_LIB, union_discriminant_field = "tag")]
+    struct Msg {
+        tag: u8,
+        #[strict_type(when = "tag == 0x01")]
+        payload: SmallBlob,
+        #[strict_type(when = "tag != 0x01")]
+        code: u16,
+    }
+
+    impl StrictSerialize for Msg {}
+    impl StrictDeserialize for Msg {}
+
+    let msg = Msg {
+        tag: 1,
+        payload: small_vec!(0xAA, 0xBB),
+        code: 7,
+    };
+    assert_eq!(msg.encoded_size(), 5);
+    let data = msg.to_strict_serialized::<16>().unwrap();
+    assert_eq!(data.as_slice(), &[1, 2, 0, 0xAA, 0xBB]);
+    assert_eq!(Msg::from_strict_serialized::<16>(data).unwrap(), Msg { code: 0, ..msg });
+
+    let msg = Msg {
+        tag: 2,
+        payload: small_vec!(0xAA),
+        code: 0x0403,
+    };
+    assert_eq!(msg.encoded_size(), 3);
+    let data = msg.to_strict_serialized::<16>().unwrap();
+    assert_eq!(data.as_slice(), &[2, 3, 4]);
+    assert_eq!(Msg::from_strict_serialized::<16>(data).unwrap(), Msg {
+        payload: none!(),
+        ..msg
+    });
+
+    Ok(())
+}
+
+#[test]
+fn single_direction() -> common::Result {
+    use strict_encoding::StrictDeserialize;
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode)]
+    #[strict_type(lib = TEST_LIB, encode_only)]
+    struct AuditEntry {
+        code: u16,
+    }
+
+    #[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, tags = repr, try_from_u8, into_u8, decode_only)]
+    #[repr(u8)]
+    enum LegacyKind {
+        #[default]
+        Plain = 1,
+    }
+
+    impl StrictSerialize for AuditEntry {}
+    impl StrictDeserialize for LegacyKind {}
+
+    let data = AuditEntry { code: 0x0201 }
+        .to_strict_serialized::<4>()
+        .unwrap();
+    assert_eq!(data.as_slice(), &[1, 2]);
+    assert_eq!(
+        LegacyKind::from_strict_serialized::<1>(confined_vec![1]).unwrap(),
+        LegacyKind::Plain
+    );
+    assert_eq!(LegacyKind::ALL_VARIANTS.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn impl_hash() -> common::Result {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, impl_hash)]
+    struct Record {
+        id: u16,
+        #[strict_type(skip)]
+        cache: u8,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, tags = order, impl_hash)]
+    enum Event {
+        #[strict_type(dumb)]
+        Start,
+        Stop(u8),
+    }
+
+    fn hash(value: &impl Hash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+    fn hash_bytes(data: &[u8]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        hasher.write(data);
+        hasher.finish()
+    }
+
+    let record = Record {
+        id: 0x0201,
+        cache: 1,
+    };
+    assert_eq!(hash(&record), hash_bytes(&[1, 2]));
+    assert_eq!(hash(&record), hash(&Record { cache: 2, ..record }));
+    assert_eq!(hash(&Event::Stop(7)), hash_bytes(&[1, 7]));
+    assert_ne!(hash(&Event::Start), hash(&Event::Stop(0)));
+
+    Ok(())
+}
+
+#[test]
+fn field_hex_literal() -> common::Result {
+    use strict_encoding::StrictDeserialize;
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Magic {
+        #[strict_type(hex_literal, const_decode = "DEADBEEF", dumb = "DEADBEEF")]
+        magic: [u8; 4],
+        #[strict_type(hex_literal, dumb = "cafe", prefix = "0A0B")]
+        id: [u8; 2],
+        #[strict_type(hex_literal = "F00D")]
+        tail: [u8; 2],
+    }
+
+    impl StrictSerialize for Magic {}
+    impl StrictDeserialize for Magic {}
+
+    let dumb = Magic::strict_dumb();
+    assert_eq!(dumb, Magic {
+        magic: [0xDE, 0xAD, 0xBE, 0xEF],
+        id: [0xCA, 0xFE],
+        tail: [0; 2],
+    });
+
+    let data = Magic {
+        magic: [0; 4],
+        id: [1, 2],
+        tail: [0; 2],
+    }
+    .to_strict_serialized::<16>()
+    .unwrap();
+    assert_eq!(data.as_slice(), &[0xDE, 0xAD, 0xBE, 0xEF, 0x0A, 0x0B, 1, 2, 0xF0, 0x0D]);
+    assert_eq!(Magic::from_strict_serialized::<16>(data).unwrap(), Magic {
+        magic: [0xDE, 0xAD, 0xBE, 0xEF],
+        id: [1, 2],
+        tail: [0xF0, 0x0D],
+    });
+
+    Ok(())
+}
+
+#[test]
+fn encode_size() -> common::Result {
+    use amplify::confinement::TinyVec;
+    use strict_encoding::StrictEncodeSize;
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictEncodeSize)]
+    #[strict_type(lib = TEST_LIB, schema_version = 1)]
+    struct Record {
+        id: u32,
+        #[strict_type(skip)]
+        cache: u64,
+        tag: Option<u16>,
+        items: TinyVec<u8>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictEncodeSize)]
+    #[strict_type(lib = TEST_LIB, tags = order)]
+    enum Message {
+        #[strict_type(dumb)]
+        Ping,
+        Data(u8, Record),
+        Ack {
+            seq: u64,
+        },
+    }
+
+    impl StrictSerialize for Record {}
+    impl StrictSerialize for Message {}
+
+    let record = Record {
+        id: 1,
+        cache: 2,
+        tag: Some(3),
+        items: tiny_vec![4, 5, 6],
+    };
+    let len = record.to_strict_serialized::<256>().unwrap().len();
+    assert_eq!(record.encoded_size(), len);
+    assert_eq!(len, 2 + 4 + 3 + 4);
+
+    for msg in [Message::Ping, Message::Data(7, record), Message::Ack { seq: 8 }] {
+        let len = msg.to_strict_serialized::<256>().unwrap().len();
+        assert_eq!(msg.encoded_size(), len);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn strict_interface() -> common::Result {
+    #[strict_interface]
+    mod protocol {
+        use super::TEST_LIB;
+
+        #[derive(Clone, PartialEq, Eq, Debug, Default)]
+        #[derive(StrictType, StrictEncode, StrictDecode)]
+        #[strict_type(lib = TEST_LIB)]
+        pub struct Ping(pub u8);
+
+        #[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+        #[derive(StrictType, StrictEncode, StrictDecode)]
+        #[strict_type(lib = TEST_LIB, tags = repr, into_u8, try_from_u8)]
+        #[repr(u8)]
+        pub enum Kind {
+            #[default]
+            Request = 1,
+            Response = 2,
+        }
+
+        // Neither private nor generic types are checked
+        #[allow(dead_code)]
+        struct Private;
+        #[allow(dead_code)]
+        pub struct Generic<T>(T);
+    }
+
+    impl StrictSerialize for protocol::Ping {}
+    impl StrictSerialize for protocol::Kind {}
+
+    assert_eq!(
+        protocol::Ping(7)
+            .to_strict_serialized::<4>()
+            .unwrap()
+            .as_slice(),
+        &[7]
+    );
+    assert_eq!(
+        protocol::Kind::Response
+            .to_strict_serialized::<4>()
+            .unwrap()
+            .as_slice(),
+        &[2]
+    );
+
+    Ok(())
+}
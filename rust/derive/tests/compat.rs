@@ -0,0 +1,55 @@
+// Derivation macro library for strict encoding.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[macro_use]
+extern crate strict_encoding_derive;
+
+use amplify::confinement::TinyString;
+
+const TEST_LIB: &str = "TestLib";
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode, StrictEncodeSize)]
+#[derive(StrictEncodeCompatTest)]
+#[strict_type(lib = TEST_LIB)]
+struct Header {
+    #[strict_type(const_decode = 2u8, dumb = 2)]
+    version: u8,
+    #[strict_type(dumb = 7)]
+    id: u32,
+    name: TinyString,
+    #[strict_type(skip)]
+    cache: Option<u64>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode, StrictEncodeSize)]
+#[derive(StrictEncodeCompatTest)]
+#[strict_type(lib = TEST_LIB, tags = order)]
+enum Message {
+    #[strict_type(dumb)]
+    Ping,
+    Data(u16, TinyString),
+    Header {
+        header: Header,
+        flag: bool,
+    },
+}
@@ -0,0 +1,64 @@
+// Derivation macro library for strict encoding.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[macro_use]
+extern crate amplify;
+#[macro_use]
+extern crate strict_encoding_derive;
+
+mod common;
+
+use strict_encoding::{assert_wire_compatible, WireFormat};
+
+const TEST_LIB: &str = "TestLib";
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[derive(StrictType)]
+#[strict_type(lib = TEST_LIB, wire_version = 3)]
+struct ClientMessage {
+    id: u32,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[derive(StrictType)]
+#[strict_type(lib = TEST_LIB, wire_version = 3)]
+struct ServerMessage(u32);
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+#[derive(StrictType)]
+#[strict_type(lib = TEST_LIB, tags = repr, into_u8, try_from_u8, wire_version = 4)]
+#[repr(u8)]
+enum Status {
+    #[default]
+    Ok = 0,
+}
+
+#[test]
+fn wire_version() -> common::Result {
+    assert_eq!(ClientMessage::WIRE_VERSION, 3);
+    assert_eq!(Status::WIRE_VERSION, 4);
+    assert_wire_compatible::<ClientMessage, ServerMessage>();
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn wire_version_mismatch() { assert_wire_compatible::<ClientMessage, Status>(); }
@@ -0,0 +1,183 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Byte strings which are encoded as bech32 strings with a fixed
+//! human-readable part.
+
+use std::io;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use amplify::confinement::SmallString;
+use bech32::primitives::decode::CheckedHrpstring;
+use bech32::{Bech32, Hrp};
+
+use crate::{
+    DecodeError, StrictDecode, StrictDumb, StrictEncode, StrictType, TypeName, TypedRead,
+    TypedWrite, LIB_NAME_STD,
+};
+
+/// Human-readable part of the bech32 strings used by [`Bech32Encoded`].
+pub trait Bech32Hrp {
+    /// Human-readable part, which must be a valid lowercase bech32 HRP.
+    const HRP: &'static str;
+}
+
+/// Bytes which are strict-encoded as a lowercase bech32 string (with the
+/// original bech32 checksum) prefixed with `H::HRP`. The string is written as
+/// a UTF-8 string with `u16` length prefix.
+///
+/// Decoding accepts only lowercase strings with the same HRP and zero padding
+/// bits, such that each value has a single encoding.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct Bech32Encoded<H: Bech32Hrp> {
+    data: Vec<u8>,
+    hrp: PhantomData<H>,
+}
+
+impl<H: Bech32Hrp> Bech32Encoded<H> {
+    /// Returns the payload bytes.
+    pub fn into_inner(self) -> Vec<u8> { self.data }
+
+    fn hrp() -> Result<Hrp, bech32::primitives::hrp::Error> { Hrp::parse(H::HRP) }
+}
+
+impl<H: Bech32Hrp> From<Vec<u8>> for Bech32Encoded<H> {
+    fn from(data: Vec<u8>) -> Self {
+        Bech32Encoded {
+            data,
+            hrp: PhantomData,
+        }
+    }
+}
+
+impl<H: Bech32Hrp> Deref for Bech32Encoded<H> {
+    type Target = [u8];
+    fn deref(&self) -> &Self::Target { &self.data }
+}
+
+impl<H: Bech32Hrp> AsRef<[u8]> for Bech32Encoded<H> {
+    fn as_ref(&self) -> &[u8] { &self.data }
+}
+
+impl<H: Bech32Hrp> StrictDumb for Bech32Encoded<H> {
+    fn strict_dumb() -> Self { Self::from(vec![]) }
+}
+impl<H: Bech32Hrp> StrictType for Bech32Encoded<H> {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+    fn strict_name() -> Option<TypeName> { Some(tn!("Bech32Encoded")) }
+}
+impl<H: Bech32Hrp> StrictEncode for Bech32Encoded<H> {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        let invalid = |err: String| io::Error::new(io::ErrorKind::InvalidInput, err);
+        let hrp = Self::hrp().map_err(|err| invalid(err.to_string()))?;
+        let s =
+            bech32::encode::<Bech32>(hrp, &self.data).map_err(|err| invalid(err.to_string()))?;
+        SmallString::try_from(s)
+            .map_err(|err| invalid(err.to_string()))?
+            .strict_encode(writer)
+    }
+}
+impl<H: Bech32Hrp> StrictDecode for Bech32Encoded<H> {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let s = SmallString::strict_decode(reader)?;
+        if s.chars().any(|c| c.is_ascii_uppercase()) {
+            return Err(DecodeError::DataIntegrityError(format!(
+                "bech32 string '{s}' is not in lowercase"
+            )));
+        }
+        let checked = CheckedHrpstring::new::<Bech32>(&s)
+            .map_err(|err| DecodeError::DataIntegrityError(err.to_string()))?;
+        let hrp = Self::hrp().map_err(|err| DecodeError::DataIntegrityError(err.to_string()))?;
+        if checked.hrp() != hrp {
+            return Err(DecodeError::DataIntegrityError(format!(
+                "bech32 string has human-readable part '{}' instead of '{}'",
+                checked.hrp(),
+                H::HRP
+            )));
+        }
+        let data = checked.byte_iter().collect::<Vec<_>>();
+        // Non-zero or excessive padding bits are dropped by the decoder, so
+        // the string must be checked to be the one produced by the encoder.
+        if bech32::encode::<Bech32>(hrp, &data).ok().as_deref() != Some(s.as_str()) {
+            return Err(DecodeError::DataIntegrityError(format!(
+                "bech32 string '{s}' is not in its canonical form"
+            )));
+        }
+        Ok(Self::from(data))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::{encode, encoding, encoding_roundtrip};
+    use crate::StrictReader;
+
+    #[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+    struct Bitcoin;
+    impl Bech32Hrp for Bitcoin {
+        const HRP: &'static str = "bc";
+    }
+
+    fn string_encoding(s: &str) -> Vec<u8> {
+        let mut data = (s.len() as u16).to_le_bytes().to_vec();
+        data.extend(s.as_bytes());
+        data
+    }
+
+    #[test]
+    fn vectors() {
+        let empty = Bech32Encoded::<Bitcoin>::from(vec![]);
+        encoding(&empty, string_encoding("bc1gmk9yu"));
+
+        let data = Bech32Encoded::<Bitcoin>::from(vec![0xDE, 0xAD, 0xBE, 0xEF]);
+        encoding(&data, string_encoding("bc1m6kmamcr0f7ys"));
+
+        encoding_roundtrip(&Bech32Encoded::<Bitcoin>::from((0u8..=255).collect::<Vec<_>>()));
+    }
+
+    #[test]
+    fn invalid() {
+        fn decode(s: &str) -> Result<Bech32Encoded<Bitcoin>, DecodeError> {
+            let data = string_encoding(s);
+            let len = data.len();
+            Bech32Encoded::strict_decode(&mut StrictReader::in_memory(data, len))
+        }
+
+        // wrong checksum
+        assert!(matches!(decode("bc1m6kmamcr0f7yq"), Err(DecodeError::DataIntegrityError(_))));
+        // uppercase form of a valid string
+        assert!(matches!(decode("BC1M6KMAMCR0F7YS"), Err(DecodeError::DataIntegrityError(_))));
+        // valid bech32 string with a different HRP
+        let testnet = bech32::encode::<Bech32>(Hrp::parse("tb").unwrap(), &[1, 2]).unwrap();
+        assert!(matches!(decode(&testnet), Err(DecodeError::DataIntegrityError(_))));
+        // non-canonical padding, which decodes to [] and [0] respectively
+        assert!(matches!(decode("bc1pc54a7w"), Err(DecodeError::DataIntegrityError(_))));
+        assert!(matches!(decode("bc1qqzm6e5wl"), Err(DecodeError::DataIntegrityError(_))));
+
+        let data = encode(&Bech32Encoded::<Bitcoin>::from(vec![1, 2]));
+        assert_eq!(
+            Bech32Encoded::<Bitcoin>::strict_decode(&mut StrictReader::in_memory(data, 64)),
+            Ok(Bech32Encoded::from(vec![1, 2]))
+        );
+    }
+}
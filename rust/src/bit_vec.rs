@@ -0,0 +1,135 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strict encoding for bit vectors from `bitvec` crate.
+
+use std::io;
+
+use bitvec::order::{BitOrder, Lsb0, Msb0};
+use bitvec::vec::BitVec;
+
+use crate::{
+    DecodeError, StrictDecode, StrictEncode, StrictType, TypeName, TypedRead, TypedWrite,
+    LIB_NAME_STD,
+};
+
+/// Maximal number of bytes in the storage of a bit vector with `u32` length.
+const MAX_BYTES: usize = (u32::MAX as usize + 7) / 8;
+
+/// Number of bytes read at once, such that a large bit count read from
+/// untrusted data doesn't cause a large allocation before the data are read.
+const CHUNK_LEN: usize = 0x1000;
+
+// The bit order changes the meaning of the storage bytes, thus each order has
+// its own type name.
+macro_rules! impl_bit_vec {
+    ($order:ty, $name:literal) => {
+        impl StrictType for BitVec<u8, $order> {
+            const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+            fn strict_name() -> Option<TypeName> { Some(tn!($name)) }
+        }
+    };
+}
+impl_bit_vec!(Lsb0, "BitVecLsb0");
+impl_bit_vec!(Msb0, "BitVecMsb0");
+
+/// [`BitVec`] with `u8` storage and [`Lsb0`] or [`Msb0`] bit order is encoded
+/// as `u32` number of bits followed by the storage bytes, in which the bits of
+/// the last byte not belonging to the vector are zeroed. Decoding fails with
+/// [`DecodeError::InvalidBitVecPadding`] if they are not zero.
+///
+/// Bit slices are not supported since they are unsized and can't implement
+/// [`StrictType`]; they can be encoded after converting into [`BitVec`].
+impl<O: BitOrder> StrictEncode for BitVec<u8, O>
+where Self: StrictType
+{
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        let len = u32::try_from(self.len()).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidInput, "bit vector has more than u32::MAX bits")
+        })?;
+        // Bit vectors created from slices may not start at the beginning of
+        // their first storage byte.
+        let mut bits = self.clone();
+        bits.force_align();
+        bits.set_uninitialized(false);
+        let writer = len.strict_encode(writer)?;
+        unsafe { writer._write_raw::<MAX_BYTES>(bits.as_raw_slice()) }
+    }
+}
+impl<O: BitOrder> StrictDecode for BitVec<u8, O>
+where Self: StrictType
+{
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let len = u32::strict_decode(reader)? as usize;
+        let byte_len = (len + 7) / 8;
+        let mut bytes = Vec::with_capacity(byte_len.min(CHUNK_LEN));
+        while bytes.len() < byte_len {
+            let chunk = (byte_len - bytes.len()).min(CHUNK_LEN);
+            bytes.extend(unsafe { reader._read_raw::<MAX_BYTES>(chunk)? });
+        }
+        let mut bits = BitVec::from_vec(bytes);
+        if bits[len..].any() {
+            return Err(DecodeError::InvalidBitVecPadding);
+        }
+        bits.truncate(len);
+        Ok(bits)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bitvec::prelude::*;
+
+    use super::*;
+    use crate::test::encoding;
+    use crate::StrictReader;
+
+    #[test]
+    fn bit_vec() {
+        encoding(&BitVec::<u8, Lsb0>::new(), [0, 0, 0, 0]);
+        encoding(&bitvec![u8, Lsb0; 1, 0, 1], [3, 0, 0, 0, 0b101]);
+        encoding(&bitvec![u8, Msb0; 1, 0, 1], [3, 0, 0, 0, 0b1010_0000]);
+        encoding(&BitVec::<u8, Lsb0>::from_vec(vec![0xCA, 0xFE]), [16, 0, 0, 0, 0xCA, 0xFE]);
+
+        let mut bits = bitvec![u8, Lsb0; 1; 8];
+        bits.truncate(2);
+        encoding(&bits, [2, 0, 0, 0, 0b11]);
+
+        let src = bitvec![u8, Lsb0; 1, 1, 1, 0, 1, 0, 0, 1, 1];
+        let sliced = BitVec::from_bitslice(&src[3..]);
+        encoding(&sliced, [6, 0, 0, 0, 0b11_0010]);
+    }
+
+    #[test]
+    fn invalid_padding() {
+        let mut reader = StrictReader::in_memory(vec![3, 0, 0, 0, 0b1101], 5);
+        assert_eq!(
+            BitVec::<u8, Lsb0>::strict_decode(&mut reader),
+            Err(DecodeError::InvalidBitVecPadding)
+        );
+    }
+
+    #[test]
+    fn order_names() {
+        assert_eq!(BitVec::<u8, Lsb0>::strict_name(), Some(tn!("BitVecLsb0")));
+        assert_eq!(BitVec::<u8, Msb0>::strict_name(), Some(tn!("BitVecMsb0")));
+    }
+}
@@ -0,0 +1,106 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strict encoding for BOLT-11 lightning invoices.
+
+use std::io;
+use std::str::FromStr;
+
+use amplify::confinement::Confined;
+use lightning_invoice::Bolt11Invoice;
+
+use crate::{
+    DecodeError, StrictDecode, StrictEncode, StrictType, TypedRead, TypedWrite, LIB_NAME_STD,
+};
+
+/// Maximal length of a BOLT-11 invoice string.
+pub const BOLT11_INVOICE_MAX_LEN: usize = 2048;
+
+/// [`Bolt11Invoice`] is encoded as its bech32 string prefixed with the string
+/// length. Decoding verifies the checksum and the invoice signature, and
+/// accepts only the canonical (lowercase) form of the string.
+impl StrictType for Bolt11Invoice {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+}
+impl StrictEncode for Bolt11Invoice {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        Confined::<String, 0, BOLT11_INVOICE_MAX_LEN>::try_from(self.to_string())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "invoice string is too long"))?
+            .strict_encode(writer)
+    }
+}
+impl StrictDecode for Bolt11Invoice {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let s = Confined::<String, 0, BOLT11_INVOICE_MAX_LEN>::strict_decode(reader)?;
+        let invoice = Bolt11Invoice::from_str(&s).map_err(|err| {
+            DecodeError::DataIntegrityError(format!("invalid BOLT-11 invoice: {err}"))
+        })?;
+        if invoice.to_string() != *s {
+            return Err(DecodeError::DataIntegrityError(s!(
+                "BOLT-11 invoice is not in its canonical lowercase form"
+            )));
+        }
+        Ok(invoice)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::encoding;
+    use crate::StrictReader;
+
+    // Test vector from BOLT-11 for testnet invoice with a fallback address
+    const INVOICE: &str = "lntb20m1pvjluezsp5zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zygshp58yjmd\
+                           an79s6qqdhdzgynm4zwqd5d7xmw5fk98klysy043l2ahrqspp5qqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfq\
+                           qqsyqcyq5rqwzqfqypqfpp3x9et2e20v6pu37c5d9vax37wxq72un989qrsgqdj545axuxtnfemtpwkc45h\
+                           x9d2ft7x04mt8q7y6t0k2dge9e7h8kpy9p34ytyslj3yu569aalz2xdk8xkd7ltxqld94u8h2esmsmacgpg\
+                           he9k8";
+
+    #[test]
+    fn invoice() {
+        let invoice = Bolt11Invoice::from_str(INVOICE).unwrap();
+        let mut data = (INVOICE.len() as u16).to_le_bytes().to_vec();
+        data.extend(INVOICE.as_bytes());
+        encoding(&invoice, data.clone());
+
+        // Uppercase form of the same invoice
+        let upper = INVOICE.to_uppercase();
+        let mut upper_data = (upper.len() as u16).to_le_bytes().to_vec();
+        upper_data.extend(upper.as_bytes());
+        let len = upper_data.len();
+        let mut reader = StrictReader::in_memory(upper_data, len);
+        assert!(matches!(
+            Bolt11Invoice::strict_decode(&mut reader),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+
+        // Corrupt the checksum
+        let last = data.len() - 1;
+        data[last] = b'q';
+        let len = data.len();
+        let mut reader = StrictReader::in_memory(data, len);
+        assert!(matches!(
+            Bolt11Invoice::strict_decode(&mut reader),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+    }
+}
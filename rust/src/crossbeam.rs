@@ -0,0 +1,103 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strict encoding for error types from `crossbeam-channel` crate.
+
+use std::io;
+
+use crossbeam_channel::{RecvError, SendError};
+
+use crate::{
+    DecodeError, StrictDecode, StrictEncode, StrictType, TypeName, TypedRead, TypedWrite,
+    LIB_NAME_STD,
+};
+
+/// Strict type representation of [`RecvError`], which is a unit type encoded
+/// as a single-variant enum (i.e. a single zero byte).
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(
+    lib = LIB_NAME_STD,
+    tags = repr,
+    into_u8,
+    try_from_u8,
+    rename = "RecvError",
+    crate = crate
+)]
+#[repr(u8)]
+enum RecvErr {
+    #[default]
+    RecvError = 0,
+}
+
+impl StrictType for RecvError {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+    fn strict_name() -> Option<TypeName> { Some(tn!("RecvError")) }
+}
+impl StrictEncode for RecvError {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_enum::<RecvErr>(RecvErr::RecvError)
+    }
+}
+impl StrictDecode for RecvError {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let _: RecvErr = reader.read_enum()?;
+        Ok(RecvError)
+    }
+}
+
+/// [`SendError`] is encoded transparently as the payload it carries.
+impl<T> StrictType for SendError<T>
+where T: StrictType
+{
+    const STRICT_LIB_NAME: &'static str = T::STRICT_LIB_NAME;
+}
+impl<T> StrictEncode for SendError<T>
+where T: StrictEncode
+{
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        self.0.strict_encode(writer)
+    }
+}
+impl<T> StrictDecode for SendError<T>
+where T: StrictDecode
+{
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        T::strict_decode(reader).map(SendError)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::TinyVec;
+
+    use super::*;
+    use crate::test::encoding;
+
+    #[test]
+    fn recv_error() { encoding(&RecvError, [0x00]) }
+
+    #[test]
+    fn send_error() {
+        let err = SendError(TinyVec::try_from(vec![0xCAu8, 0xFE]).unwrap());
+        encoding(&err, [0x02, 0xCA, 0xFE]);
+    }
+}
@@ -0,0 +1,154 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strict encoding for keys and signatures from `ed25519-dalek` crate.
+
+use std::io;
+
+use ed25519_dalek::{Signature, SigningKey, VerifyingKey};
+
+use crate::{
+    DecodeError, StrictDecode, StrictEncode, StrictEncodeSize, StrictEncodedLen, StrictType,
+    TypeName, TypedRead, TypedWrite,
+};
+
+/// [`VerifyingKey`] (public key) is encoded as its 32-byte compressed
+/// Edwards point. Decoding fails if the bytes do not represent a point on the
+/// curve.
+impl StrictType for VerifyingKey {
+    const STRICT_LIB_NAME: &'static str = <[u8; Self::ENCODED_LEN]>::STRICT_LIB_NAME;
+    fn strict_name() -> Option<TypeName> { Some(tn!("Ed25519PublicKey")) }
+}
+impl StrictEncode for VerifyingKey {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        self.as_bytes().strict_encode(writer)
+    }
+}
+impl StrictDecode for VerifyingKey {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let bytes = <[u8; Self::ENCODED_LEN]>::strict_decode(reader)?;
+        VerifyingKey::from_bytes(&bytes).map_err(|err| {
+            DecodeError::DataIntegrityError(format!("invalid Ed25519 public key: {err}"))
+        })
+    }
+}
+impl StrictEncodeSize for VerifyingKey {
+    fn encoded_size(&self) -> usize { Self::ENCODED_LEN }
+}
+impl StrictEncodedLen for VerifyingKey {
+    const ENCODED_LEN: usize = 32;
+}
+
+/// [`SigningKey`] (secret key) is encoded as its 32-byte seed, from which the
+/// public key is derived on decoding.
+impl StrictType for SigningKey {
+    const STRICT_LIB_NAME: &'static str = <[u8; Self::ENCODED_LEN]>::STRICT_LIB_NAME;
+    fn strict_name() -> Option<TypeName> { Some(tn!("Ed25519SecretKey")) }
+}
+impl StrictEncode for SigningKey {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        self.as_bytes().strict_encode(writer)
+    }
+}
+impl StrictDecode for SigningKey {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let bytes = <[u8; Self::ENCODED_LEN]>::strict_decode(reader)?;
+        Ok(SigningKey::from_bytes(&bytes))
+    }
+}
+impl StrictEncodeSize for SigningKey {
+    fn encoded_size(&self) -> usize { Self::ENCODED_LEN }
+}
+impl StrictEncodedLen for SigningKey {
+    const ENCODED_LEN: usize = 32;
+}
+
+/// [`Signature`] is encoded as its 64 raw bytes.
+impl StrictType for Signature {
+    const STRICT_LIB_NAME: &'static str = <[u8; Self::ENCODED_LEN]>::STRICT_LIB_NAME;
+    fn strict_name() -> Option<TypeName> { Some(tn!("Ed25519Signature")) }
+}
+impl StrictEncode for Signature {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        self.to_bytes().strict_encode(writer)
+    }
+}
+impl StrictDecode for Signature {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let bytes = <[u8; Self::ENCODED_LEN]>::strict_decode(reader)?;
+        Ok(Signature::from_bytes(&bytes))
+    }
+}
+impl StrictEncodeSize for Signature {
+    fn encoded_size(&self) -> usize { Self::ENCODED_LEN }
+}
+impl StrictEncodedLen for Signature {
+    const ENCODED_LEN: usize = 64;
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::hex::FromHex;
+    use ed25519_dalek::Signer;
+
+    use super::*;
+    use crate::test::{encode, encoding};
+    use crate::StrictReader;
+
+    // Test 1 from RFC 8032, section 7.1
+    const SECRET: &str = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+    const PUBLIC: &str = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+    const SIGNATURE: &str = "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b";
+
+    #[test]
+    fn rfc8032() {
+        let secret = <[u8; 32]>::from_hex(SECRET).unwrap();
+        let public = <[u8; 32]>::from_hex(PUBLIC).unwrap();
+        let signature = <[u8; 64]>::from_hex(SIGNATURE).unwrap();
+
+        let signing_key = SigningKey::from_bytes(&secret);
+        let verifying_key = signing_key.verifying_key();
+        let sig = signing_key.sign(b"");
+
+        encoding(&signing_key, secret);
+        encoding(&verifying_key, public);
+        encoding(&sig, signature);
+        assert_eq!(signing_key.encoded_size(), encode(&signing_key).len());
+        assert_eq!(verifying_key.encoded_size(), encode(&verifying_key).len());
+        assert_eq!(sig.encoded_size(), encode(&sig).len());
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(VerifyingKey::strict_name(), Some(tn!("Ed25519PublicKey")));
+        assert_eq!(SigningKey::strict_name(), Some(tn!("Ed25519SecretKey")));
+        assert_eq!(Signature::strict_name(), Some(tn!("Ed25519Signature")));
+    }
+
+    #[test]
+    fn invalid_public_key() {
+        let mut reader = StrictReader::in_memory(vec![0x02; 32], 32);
+        assert!(matches!(
+            VerifyingKey::strict_decode(&mut reader),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+    }
+}
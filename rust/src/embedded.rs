@@ -19,12 +19,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::hash::Hash;
-use std::io;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::task::Poll;
+use std::{fmt, io};
 
 use amplify::ascii::AsciiString;
-use amplify::confinement::Confined;
+use amplify::confinement::{self, Confined, SmallString};
 #[cfg(feature = "float")]
 use amplify::num::apfloat::{ieee, Float};
 use amplify::num::{i1024, i256, i512, u1024, u24, u256, u512};
@@ -33,9 +37,9 @@ use amplify::{Array, Wrapper};
 use crate::constants::*;
 use crate::stl::AsciiSym;
 use crate::{
-    DecodeError, DefineUnion, ReadTuple, ReadUnion, Sizing, StrictDecode, StrictDumb, StrictEncode,
-    StrictProduct, StrictStruct, StrictSum, StrictTuple, StrictType, StrictUnion, TypeName,
-    TypedRead, TypedWrite, WriteTuple, WriteUnion, LIB_EMBEDDED,
+    DecodeError, DefineUnion, EncodeError, ReadTuple, ReadUnion, SchemaHasher, Sizing,
+    StrictDecode, StrictDumb, StrictEncode, StrictProduct, StrictStruct, StrictSum, StrictTuple,
+    StrictType, StrictUnion, TypeName, TypedRead, TypedWrite, WriteTuple, WriteUnion, LIB_EMBEDDED,
 };
 
 #[derive(
@@ -104,6 +108,39 @@ macro_rules! encode_float {
     };
 }
 
+macro_rules! encode_half {
+    ($ty:ty, $id:ident) => {
+        #[cfg(feature = "half")]
+        impl $crate::StrictType for $ty {
+            const STRICT_LIB_NAME: &'static str = $crate::LIB_EMBEDDED;
+        }
+        #[cfg(feature = "half")]
+        impl $crate::StrictEncode for $ty {
+            fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+                unsafe {
+                    writer
+                        .register_primitive($id)
+                        ._write_raw_array(self.to_le_bytes())
+                }
+            }
+        }
+        #[cfg(feature = "half")]
+        impl $crate::StrictDecode for $ty {
+            fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+                let buf = unsafe { reader._read_raw_array::<2>()? };
+                let val = Self::from_le_bytes(buf);
+                if val.is_nan() {
+                    return Err(DecodeError::DataIntegrityError(format!(
+                        "NaN value for {} type",
+                        stringify!($ty)
+                    )));
+                }
+                Ok(val)
+            }
+        }
+    };
+}
+
 encode_num!(u8, U8);
 encode_num!(u16, U16);
 encode_num!(u24, U24);
@@ -130,10 +167,16 @@ encode_float!(ieee::X87DoubleExtended, 10, F80);
 encode_float!(ieee::Quad, 16, F128);
 encode_float!(ieee::Oct, 32, F256);
 
+encode_half!(half::f16, F16);
+encode_half!(half::bf16, F16B);
+
 impl<T> StrictType for Box<T>
 where T: StrictType
 {
     const STRICT_LIB_NAME: &'static str = T::STRICT_LIB_NAME;
+    fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) {
+        T::strict_schema(hasher, PhantomData)
+    }
 }
 impl<T> StrictSum for Box<T>
 where T: StrictSum
@@ -168,11 +211,64 @@ where T: StrictDecode
     }
 }
 
+/// Values wiped on drop with [`zeroize::Zeroizing`] are encoded as the value
+/// they contain.
+#[cfg(feature = "zeroize")]
+impl<T> StrictType for zeroize::Zeroizing<T>
+where T: StrictType + zeroize::Zeroize
+{
+    const STRICT_LIB_NAME: &'static str = T::STRICT_LIB_NAME;
+    fn strict_name() -> Option<TypeName> { T::strict_name() }
+    fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) {
+        T::strict_schema(hasher, PhantomData)
+    }
+}
+#[cfg(feature = "zeroize")]
+impl<T> StrictEncode for zeroize::Zeroizing<T>
+where T: StrictEncode + zeroize::Zeroize
+{
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        (**self).strict_encode(writer)
+    }
+}
+#[cfg(feature = "zeroize")]
+impl<T> StrictDecode for zeroize::Zeroizing<T>
+where T: StrictDecode + zeroize::Zeroize
+{
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        T::strict_decode(reader).map(zeroize::Zeroizing::new)
+    }
+}
+
+/// Pinned references are encoded as the value they point to. Same as for plain
+/// references, there is no [`StrictDecode`] implementation, since a reference
+/// can't be constructed from decoded data.
+impl<T> StrictType for Pin<&T>
+where T: StrictType + Unpin
+{
+    const STRICT_LIB_NAME: &'static str = T::STRICT_LIB_NAME;
+    fn strict_name() -> Option<TypeName> { T::strict_name() }
+    fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) {
+        T::strict_schema(hasher, PhantomData)
+    }
+}
+impl<T> StrictEncode for Pin<&T>
+where T: StrictEncode + Unpin
+{
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        Pin::into_inner(*self).strict_encode(writer)
+    }
+}
+
 impl<T> StrictType for Option<T>
 where T: StrictType
 {
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
     fn strict_name() -> Option<TypeName> { None }
+    fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) {
+        hasher.write_str("Option");
+        hasher.write_type::<T>();
+    }
 }
 impl<T> StrictSum for Option<T>
 where T: StrictType
@@ -186,20 +282,26 @@ where T: StrictType
     }
 }
 impl<T> StrictUnion for Option<T> where T: StrictType {}
+pub(crate) fn write_option<T: StrictEncode + StrictDumb, W: TypedWrite>(
+    writer: W,
+    value: Option<&T>,
+) -> io::Result<W> {
+    writer.write_union::<Option<T>>(|u| {
+        let u = u
+            .define_unit(vname!("none"))
+            .define_newtype::<T>(vname!("some"))
+            .complete();
+
+        Ok(match value {
+            None => u.write_unit(vname!("none")),
+            Some(val) => u.write_newtype(vname!("some"), val),
+        }?
+        .complete())
+    })
+}
 impl<T: StrictEncode + StrictDumb> StrictEncode for Option<T> {
     fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
-        writer.write_union::<Self>(|u| {
-            let u = u
-                .define_unit(vname!("none"))
-                .define_newtype::<T>(vname!("some"))
-                .complete();
-
-            Ok(match self {
-                None => u.write_unit(vname!("none")),
-                Some(val) => u.write_newtype(vname!("some"), val),
-            }?
-            .complete())
-        })
+        write_option(writer, self.as_ref())
     }
 }
 impl<T: StrictDecode> StrictDecode for Option<T> {
@@ -212,9 +314,40 @@ impl<T: StrictDecode> StrictDecode for Option<T> {
     }
 }
 
+/// [`Poll`] has the same strict type as [`Option`], where [`Poll::Pending`]
+/// is encoded as `none` and [`Poll::Ready`] as `some`.
+impl<T> StrictType for Poll<T>
+where T: StrictType
+{
+    const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+    fn strict_name() -> Option<TypeName> { None }
+    fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) {
+        hasher.write_str("Poll");
+        hasher.write_type::<T>();
+    }
+}
+impl<T: StrictEncode + StrictDumb> StrictEncode for Poll<T> {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        let value = match self {
+            Poll::Pending => None,
+            Poll::Ready(val) => Some(val),
+        };
+        write_option(writer, value)
+    }
+}
+impl<T: StrictDecode> StrictDecode for Poll<T> {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        Ok(match Option::<T>::strict_decode(reader)? {
+            None => Poll::Pending,
+            Some(val) => Poll::Ready(val),
+        })
+    }
+}
+
 impl StrictType for () {
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
     fn strict_name() -> Option<TypeName> { None }
+    fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) { hasher.write_str("Unit") }
 }
 impl StrictEncode for () {
     fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
@@ -228,6 +361,11 @@ impl StrictDecode for () {
 impl<A: StrictType, B: StrictType> StrictType for (A, B) {
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
     fn strict_name() -> Option<TypeName> { None }
+    fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) {
+        hasher.write_str("Tuple");
+        hasher.write_type::<A>();
+        hasher.write_type::<B>();
+    }
 }
 impl<A: StrictType + Default, B: StrictType + Default> StrictProduct for (A, B) {}
 impl<A: StrictType + Default, B: StrictType + Default> StrictTuple for (A, B) {
@@ -251,6 +389,12 @@ impl<A: StrictDecode + Default, B: StrictDecode + Default> StrictDecode for (A,
 impl<A: StrictType, B: StrictType, C: StrictType> StrictType for (A, B, C) {
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
     fn strict_name() -> Option<TypeName> { None }
+    fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) {
+        hasher.write_str("Tuple");
+        hasher.write_type::<A>();
+        hasher.write_type::<B>();
+        hasher.write_type::<C>();
+    }
 }
 impl<A: StrictType + Default, B: StrictType + Default, C: StrictType + Default> StrictProduct
     for (A, B, C)
@@ -289,6 +433,11 @@ impl<A: StrictDecode + Default, B: StrictDecode + Default, C: StrictDecode + Def
 impl<T: StrictType + Copy + StrictDumb, const LEN: usize> StrictType for [T; LEN] {
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
     fn strict_name() -> Option<TypeName> { None }
+    fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) {
+        hasher.write_str("Array");
+        hasher.write_u64(LEN as u64);
+        hasher.write_type::<T>();
+    }
 }
 impl<T: StrictEncode + Copy + StrictDumb, const LEN: usize> StrictEncode for [T; LEN] {
     fn strict_encode<W: TypedWrite>(&self, mut writer: W) -> io::Result<W> {
@@ -317,6 +466,11 @@ impl<T: StrictDecode + Copy + StrictDumb, const LEN: usize> StrictDecode for [T;
 impl<T: StrictType + StrictDumb + Copy, const LEN: usize> StrictType for Array<T, LEN> {
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
     fn strict_name() -> Option<TypeName> { None }
+    fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) {
+        hasher.write_str("Array");
+        hasher.write_u64(LEN as u64);
+        hasher.write_type::<T>();
+    }
 }
 impl<T: StrictEncode + StrictDumb + Copy, const LEN: usize> StrictEncode for Array<T, LEN> {
     fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
@@ -332,6 +486,11 @@ impl<T: StrictDecode + StrictDumb + Copy, const LEN: usize> StrictDecode for Arr
 impl<const MIN_LEN: usize, const MAX_LEN: usize> StrictType for Confined<String, MIN_LEN, MAX_LEN> {
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
     fn strict_name() -> Option<TypeName> { None }
+    fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) {
+        hasher.write_str("String");
+        hasher.write_u64(MIN_LEN as u64);
+        hasher.write_u64(MAX_LEN as u64);
+    }
 }
 impl<const MIN_LEN: usize, const MAX_LEN: usize> StrictEncode
     for Confined<String, MIN_LEN, MAX_LEN>
@@ -354,11 +513,34 @@ impl<const MIN_LEN: usize, const MAX_LEN: usize> StrictDecode
     }
 }
 
+/// Format arguments produced by [`format_args!`] are encoded as the formatted
+/// UTF-8 string, in the same way as [`SmallString`]. Formatting into a string
+/// longer than `u16::MAX` bytes fails with [`io::ErrorKind::InvalidInput`].
+impl StrictType for fmt::Arguments<'_> {
+    const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+    fn strict_name() -> Option<TypeName> { None }
+    fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) {
+        SmallString::strict_schema(hasher, PhantomData)
+    }
+}
+impl StrictEncode for fmt::Arguments<'_> {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        SmallString::try_from(self.to_string())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?
+            .strict_encode(writer)
+    }
+}
+
 impl<const MIN_LEN: usize, const MAX_LEN: usize> StrictType
     for Confined<AsciiString, MIN_LEN, MAX_LEN>
 {
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
     fn strict_name() -> Option<TypeName> { None }
+    fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) {
+        hasher.write_str("AsciiString");
+        hasher.write_u64(MIN_LEN as u64);
+        hasher.write_u64(MAX_LEN as u64);
+    }
 }
 impl<const MIN_LEN: usize, const MAX_LEN: usize> StrictEncode
     for Confined<AsciiString, MIN_LEN, MAX_LEN>
@@ -389,6 +571,12 @@ impl<T: StrictType, const MIN_LEN: usize, const MAX_LEN: usize> StrictType
 {
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
     fn strict_name() -> Option<TypeName> { None }
+    fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) {
+        hasher.write_str("List");
+        hasher.write_u64(MIN_LEN as u64);
+        hasher.write_u64(MAX_LEN as u64);
+        hasher.write_type::<T>();
+    }
 }
 impl<T: StrictEncode + StrictDumb, const MIN_LEN: usize, const MAX_LEN: usize> StrictEncode
     for Confined<Vec<T>, MIN_LEN, MAX_LEN>
@@ -419,11 +607,62 @@ impl<T: StrictDecode, const MIN_LEN: usize, const MAX_LEN: usize> StrictDecode
     }
 }
 
+/// [`VecDeque`] is encoded as its logical sequence of items, exactly as a
+/// `Vec` of the same items. The position of the items inside the ring buffer
+/// is not preserved; the decoded deque has its capacity pre-allocated to hold
+/// all decoded items.
+impl<T: StrictType, const MIN_LEN: usize, const MAX_LEN: usize> StrictType
+    for Confined<VecDeque<T>, MIN_LEN, MAX_LEN>
+{
+    const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+    fn strict_name() -> Option<TypeName> { None }
+    fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) {
+        hasher.write_str("List");
+        hasher.write_u64(MIN_LEN as u64);
+        hasher.write_u64(MAX_LEN as u64);
+        hasher.write_type::<T>();
+    }
+}
+impl<T: StrictEncode + StrictDumb, const MIN_LEN: usize, const MAX_LEN: usize> StrictEncode
+    for Confined<VecDeque<T>, MIN_LEN, MAX_LEN>
+{
+    fn strict_encode<W: TypedWrite>(&self, mut writer: W) -> io::Result<W> {
+        let sizing = Sizing::new(MIN_LEN as u64, MAX_LEN as u64);
+        writer = unsafe {
+            writer = writer.write_collection::<VecDeque<T>, MIN_LEN, MAX_LEN>(self)?;
+            if T::strict_name() == u8::strict_name() {
+                writer.register_list(&Byte::strict_dumb(), sizing)
+            } else {
+                writer.register_list(&T::strict_dumb(), sizing)
+            }
+        };
+        Ok(writer)
+    }
+}
+impl<T: StrictDecode, const MIN_LEN: usize, const MAX_LEN: usize> StrictDecode
+    for Confined<VecDeque<T>, MIN_LEN, MAX_LEN>
+{
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let len = unsafe { reader._read_raw_len::<MAX_LEN>()? };
+        let mut col = VecDeque::<T>::with_capacity(len);
+        for _ in 0..len {
+            col.push_back(StrictDecode::strict_decode(reader)?);
+        }
+        Confined::try_from(col).map_err(DecodeError::from)
+    }
+}
+
 impl<T: StrictType + Ord, const MIN_LEN: usize, const MAX_LEN: usize> StrictType
     for Confined<BTreeSet<T>, MIN_LEN, MAX_LEN>
 {
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
     fn strict_name() -> Option<TypeName> { None }
+    fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) {
+        hasher.write_str("Set");
+        hasher.write_u64(MIN_LEN as u64);
+        hasher.write_u64(MAX_LEN as u64);
+        hasher.write_type::<T>();
+    }
 }
 impl<T: StrictEncode + Ord + StrictDumb, const MIN_LEN: usize, const MAX_LEN: usize> StrictEncode
     for Confined<BTreeSet<T>, MIN_LEN, MAX_LEN>
@@ -461,6 +700,13 @@ impl<K: StrictType + Ord + Hash, V: StrictType, const MIN_LEN: usize, const MAX_
 {
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
     fn strict_name() -> Option<TypeName> { None }
+    fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) {
+        hasher.write_str("Map");
+        hasher.write_u64(MIN_LEN as u64);
+        hasher.write_u64(MAX_LEN as u64);
+        hasher.write_type::<K>();
+        hasher.write_type::<V>();
+    }
 }
 impl<
     K: StrictEncode + Ord + Hash + StrictDumb,
@@ -509,3 +755,168 @@ impl<
         Confined::try_from(col).map_err(DecodeError::from)
     }
 }
+
+/// Map which may contain at most `MAX` entries. Unlike a confined map it can
+/// be filled with any number of entries; encoding fails with
+/// [`EncodeError::MapTooLarge`] and decoding with [`DecodeError::MapTooLarge`]
+/// if the limit is exceeded. It is encoded in the same way as a confined map
+/// with the same limit.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+pub struct BoundedMap<K, V, const MAX: usize>(BTreeMap<K, V>);
+
+impl<K, V, const MAX: usize> BoundedMap<K, V, MAX> {
+    /// Returns the wrapped map.
+    pub fn into_inner(self) -> BTreeMap<K, V> { self.0 }
+}
+
+impl<K, V, const MAX: usize> From<BTreeMap<K, V>> for BoundedMap<K, V, MAX> {
+    fn from(map: BTreeMap<K, V>) -> Self { Self(map) }
+}
+
+impl<K, V, const MAX: usize> Deref for BoundedMap<K, V, MAX> {
+    type Target = BTreeMap<K, V>;
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl<K, V, const MAX: usize> DerefMut for BoundedMap<K, V, MAX> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.0 }
+}
+
+impl<K: StrictType + Ord + Hash, V: StrictType, const MAX: usize> StrictType
+    for BoundedMap<K, V, MAX>
+{
+    const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+    fn strict_name() -> Option<TypeName> { None }
+    fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) {
+        Confined::<BTreeMap<K, V>, 0, MAX>::strict_schema(hasher, PhantomData)
+    }
+}
+impl<K: StrictEncode + Ord + Hash + StrictDumb, V: StrictEncode + StrictDumb, const MAX: usize>
+    StrictEncode for BoundedMap<K, V, MAX>
+{
+    fn strict_encode<W: TypedWrite>(&self, mut writer: W) -> io::Result<W> {
+        if self.len() > MAX {
+            return Err(EncodeError::MapTooLarge {
+                actual: self.len(),
+                max: MAX,
+            }
+            .into());
+        }
+        unsafe {
+            writer = writer._write_raw_len::<MAX>(self.len())?;
+        }
+        for (k, v) in self.iter() {
+            writer = k.strict_encode(writer)?;
+            writer = v.strict_encode(writer)?
+        }
+        Ok(unsafe {
+            writer.register_map(&K::strict_dumb(), &V::strict_dumb(), Sizing::new(0, MAX as u64))
+        })
+    }
+}
+impl<K: StrictDecode + Ord + Hash + StrictDumb, V: StrictDecode + StrictDumb, const MAX: usize>
+    StrictDecode for BoundedMap<K, V, MAX>
+{
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        // the length prefix is checked against the limit before any entry is read
+        Confined::<BTreeMap<K, V>, 0, MAX>::strict_decode(reader)
+            .map(|map| Self(map.into_inner()))
+            .map_err(|err| match err {
+                DecodeError::Confinement(confinement::Error::Oversize { len, max_len }) => {
+                    DecodeError::MapTooLarge {
+                        actual: len,
+                        max: max_len,
+                    }
+                }
+                err => err,
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::{encode, encoding};
+
+    #[test]
+    fn pin_ref() {
+        let val = 0xA5u8;
+        assert_eq!(encode(&Pin::new(&val)), [0xA5]);
+    }
+
+    #[test]
+    fn format_args() {
+        use crate::strict_encode_to_writer;
+
+        let name = "world";
+        let mut data = vec![];
+        strict_encode_to_writer(&format_args!("hello {name}"), &mut data).unwrap();
+        assert_eq!(data, encode(&SmallString::try_from(s!("hello world")).unwrap()));
+        assert_eq!(data, b"\x0B\x00hello world");
+    }
+
+    #[test]
+    fn vec_deque() {
+        use amplify::confinement::{TinyDeque, TinyVec};
+
+        let mut deque = VecDeque::with_capacity(4);
+        deque.extend([3u16, 4]);
+        deque.push_front(2);
+        deque.push_front(1);
+        let deque = TinyDeque::try_from(deque).unwrap();
+        let vec = TinyVec::try_from(vec![1u16, 2, 3, 4]).unwrap();
+        encoding(&deque, encode(&vec));
+    }
+
+    #[test]
+    fn bounded_map() {
+        use crate::{StrictReader, StrictWriter};
+
+        let mut map = BoundedMap::<u8, u16, 2>::from(bmap! { 1 => 0xA5, 2 => 0xCAFE });
+        encoding(&map, [0x02, 0x01, 0xA5, 0x00, 0x02, 0xFE, 0xCA]);
+
+        map.insert(3, 0);
+        let err = map.strict_encode(StrictWriter::in_memory(256)).unwrap_err();
+        assert_eq!(
+            err.get_ref()
+                .and_then(|err| err.downcast_ref::<EncodeError>()),
+            Some(&EncodeError::MapTooLarge { actual: 3, max: 2 })
+        );
+
+        let mut reader = StrictReader::in_memory(vec![200], 0xFF);
+        assert_eq!(
+            BoundedMap::<u8, u16, 2>::strict_decode(&mut reader),
+            Err(DecodeError::MapTooLarge {
+                actual: 200,
+                max: 2
+            })
+        );
+    }
+
+    #[test]
+    fn poll() {
+        encoding(&Poll::<u8>::Pending, [0x00]);
+        encoding(&Poll::Ready(0xA5u8), [0x01, 0xA5]);
+    }
+
+    #[test]
+    #[cfg(feature = "half")]
+    fn half() {
+        use half::{bf16, f16};
+
+        use crate::StrictReader;
+
+        encoding(&f16::ONE, [0x00, 0x3C]);
+        encoding(&f16::NEG_INFINITY, [0x00, 0xFC]);
+        encoding(&bf16::ONE, [0x80, 0x3F]);
+        encoding(&bf16::MIN_POSITIVE, [0x80, 0x00]);
+
+        let mut reader = StrictReader::in_memory(vec![0x00, 0x7E], 2);
+        assert!(matches!(f16::strict_decode(&mut reader), Err(DecodeError::DataIntegrityError(_))));
+        let mut reader = StrictReader::in_memory(vec![0xC0, 0x7F], 2);
+        assert!(matches!(
+            bf16::strict_decode(&mut reader),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+    }
+}
@@ -49,6 +49,9 @@ pub enum DecodeError {
     /// key occurs multiple times in a map
     RepeatedMapValue,
 
+    /// attribute key `{0}` occurs multiple times in an attribute map
+    DuplicateAttributeKey(String),
+
     /// unsupported value `{1}` for enum `{0}` encountered during decode
     /// operation
     EnumTagNotKnown(String, u8),
@@ -69,6 +72,97 @@ pub enum DecodeError {
 
     /// data integrity problem during strict decoding operation.\nDetails: {0}
     DataIntegrityError(String),
+
+    /// schema version {found} doesn't match the expected version {expected}
+    IncompatibleSchemaVersion { expected: u16, found: u16 },
+
+    /// schema hash {found:02x?} doesn't match the hash {expected:02x?} of the
+    /// current type schema
+    SchemaHashMismatch { expected: [u8; 4], found: [u8; 4] },
+
+    /// header field has type id {found} instead of the expected {expected}
+    UnexpectedHeaderType { expected: u8, found: u8 },
+
+    /// field `{field}` is preceded by bytes {got:02x?} instead of the
+    /// expected prefix {expected:02x?}
+    InvalidFieldPrefix {
+        field: String,
+        expected: Vec<u8>,
+        got: Vec<u8>,
+    },
+
+    /// field `{field}` has value {got} instead of the expected constant
+    /// {expected}
+    ConstantMismatch {
+        field: String,
+        expected: String,
+        got: String,
+    },
+
+    /// bit vector has non-zero bits in the padding of its last byte
+    InvalidBitVecPadding,
+
+    /// minimal value exceeds maximal value in a min-max pair
+    InvertedMinMax,
+
+    /// nonce value was already used; possible replay attack
+    ReplayedNonce,
+
+    /// encoded map has {actual} entries, which exceeds the limit of {max}
+    /// entries
+    MapTooLarge { actual: usize, max: usize },
+}
+
+/// Errors detected by the encoders, which are returned inside [`io::Error`] of
+/// [`io::ErrorKind::InvalidData`] kind.
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum EncodeError {
+    #[display(inner)]
+    Io(IoError),
+
+    /// value is encoded into {actual} bytes instead of its fixed encoding
+    /// length {expected}
+    FixedLenMismatch { expected: usize, actual: usize },
+
+    /// field `{field}` has value {count}, which doesn't match the number {len}
+    /// of the items it counts
+    CountMismatch {
+        field: String,
+        count: String,
+        len: usize,
+    },
+
+    /// field `{field}` can't hold the number {len} of the items it counts
+    CountOverflow { field: String, len: usize },
+
+    /// map has {actual} entries, which exceeds the limit of {max} entries
+    MapTooLarge { actual: usize, max: usize },
+}
+
+impl From<io::Error> for EncodeError {
+    /// Recovers encoder errors returned by [`StrictEncode`] implementations
+    /// inside [`io::Error`].
+    ///
+    /// [`StrictEncode`]: crate::StrictEncode
+    fn from(err: io::Error) -> Self {
+        match err
+            .get_ref()
+            .and_then(|inner| inner.downcast_ref::<EncodeError>())
+        {
+            Some(inner) => inner.clone(),
+            None => EncodeError::Io(err.into()),
+        }
+    }
+}
+
+impl From<EncodeError> for io::Error {
+    fn from(err: EncodeError) -> Self {
+        match err {
+            EncodeError::Io(err) => err.into(),
+            err => io::Error::new(io::ErrorKind::InvalidData, err),
+        }
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
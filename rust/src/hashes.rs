@@ -0,0 +1,229 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Typed 32-byte cryptographic hash values.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use amplify::hex::{self, FromHex, ToHex};
+use digest::consts::U32;
+use digest::{Digest, OutputSizeUser};
+
+use crate::{
+    DecodeError, ReadTuple, StrictDecode, StrictEncode, StrictEncodeSize, StrictEncodedLen,
+    StrictProduct, StrictTuple, StrictType, TypeName, TypedRead, TypedWrite, WriteTuple,
+    LIB_NAME_STD,
+};
+
+/// Hash function providing the strict type name for its [`StrictHash`]
+/// values, such that values of different hash functions are distinct types.
+pub trait StrictHashName {
+    /// Strict type name of the hash values, like `Sha256Hash`.
+    const STRICT_HASH_NAME: &'static str;
+}
+
+#[cfg(feature = "sha2")]
+impl StrictHashName for sha2::Sha256 {
+    const STRICT_HASH_NAME: &'static str = "Sha256Hash";
+}
+
+#[cfg(feature = "blake3")]
+impl StrictHashName for blake3::Hasher {
+    const STRICT_HASH_NAME: &'static str = "Blake3Hash";
+}
+
+/// 32-byte hash value produced by the hash function `H`.
+///
+/// The hash function is a type-level marker only: the value is encoded as a
+/// tuple with a single 32-byte array field and does not depend on `H`. The
+/// strict type name is provided by `H` via [`StrictHashName`].
+pub struct StrictHash<H>([u8; 32], PhantomData<H>)
+where H: Digest + OutputSizeUser<OutputSize = U32>;
+
+/// SHA-256 hash value.
+#[cfg(feature = "sha2")]
+pub type Sha256Hash = StrictHash<sha2::Sha256>;
+
+/// BLAKE3 hash value.
+#[cfg(feature = "blake3")]
+pub type Blake3Hash = StrictHash<blake3::Hasher>;
+
+impl<H> StrictHash<H>
+where H: Digest + OutputSizeUser<OutputSize = U32>
+{
+    /// Hashes `data` with the hash function `H`.
+    pub fn digest(data: impl AsRef<[u8]>) -> Self { Self::from_byte_array(H::digest(data).into()) }
+
+    pub const fn from_byte_array(bytes: [u8; 32]) -> Self { StrictHash(bytes, PhantomData) }
+
+    pub const fn to_byte_array(&self) -> [u8; 32] { self.0 }
+
+    pub const fn as_byte_array(&self) -> &[u8; 32] { &self.0 }
+}
+
+impl<H> From<[u8; 32]> for StrictHash<H>
+where H: Digest + OutputSizeUser<OutputSize = U32>
+{
+    fn from(bytes: [u8; 32]) -> Self { Self::from_byte_array(bytes) }
+}
+
+impl<H> AsRef<[u8]> for StrictHash<H>
+where H: Digest + OutputSizeUser<OutputSize = U32>
+{
+    fn as_ref(&self) -> &[u8] { &self.0 }
+}
+
+impl<H> Copy for StrictHash<H> where H: Digest + OutputSizeUser<OutputSize = U32> {}
+impl<H> Clone for StrictHash<H>
+where H: Digest + OutputSizeUser<OutputSize = U32>
+{
+    fn clone(&self) -> Self { *self }
+}
+
+impl<H> PartialEq for StrictHash<H>
+where H: Digest + OutputSizeUser<OutputSize = U32>
+{
+    fn eq(&self, other: &Self) -> bool { self.0 == other.0 }
+}
+impl<H> Eq for StrictHash<H> where H: Digest + OutputSizeUser<OutputSize = U32> {}
+
+impl<H> PartialOrd for StrictHash<H>
+where H: Digest + OutputSizeUser<OutputSize = U32>
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+impl<H> Ord for StrictHash<H>
+where H: Digest + OutputSizeUser<OutputSize = U32>
+{
+    fn cmp(&self, other: &Self) -> Ordering { self.0.cmp(&other.0) }
+}
+
+impl<H> Hash for StrictHash<H>
+where H: Digest + OutputSizeUser<OutputSize = U32>
+{
+    fn hash<S: Hasher>(&self, state: &mut S) { self.0.hash(state) }
+}
+
+/// Produces all-zero hash value.
+impl<H> Default for StrictHash<H>
+where H: Digest + OutputSizeUser<OutputSize = U32>
+{
+    fn default() -> Self { Self::from_byte_array([0u8; 32]) }
+}
+
+impl<H> Debug for StrictHash<H>
+where H: Digest + OutputSizeUser<OutputSize = U32>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("StrictHash").field(&self.0.to_hex()).finish()
+    }
+}
+
+impl<H> Display for StrictHash<H>
+where H: Digest + OutputSizeUser<OutputSize = U32>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { f.write_str(&self.0.to_hex()) }
+}
+
+impl<H> FromStr for StrictHash<H>
+where H: Digest + OutputSizeUser<OutputSize = U32>
+{
+    type Err = hex::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        <[u8; 32]>::from_hex(s).map(Self::from_byte_array)
+    }
+}
+
+impl<H> StrictType for StrictHash<H>
+where H: Digest + OutputSizeUser<OutputSize = U32> + StrictHashName
+{
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+    fn strict_name() -> Option<TypeName> { Some(tn!(H::STRICT_HASH_NAME)) }
+}
+impl<H> StrictProduct for StrictHash<H> where H: Digest + OutputSizeUser<OutputSize = U32> + StrictHashName
+{}
+impl<H> StrictTuple for StrictHash<H>
+where H: Digest + OutputSizeUser<OutputSize = U32> + StrictHashName
+{
+    const FIELD_COUNT: u8 = 1;
+}
+impl<H> StrictEncode for StrictHash<H>
+where H: Digest + OutputSizeUser<OutputSize = U32> + StrictHashName
+{
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_tuple::<Self>(|w| Ok(w.write_field(&self.0)?.complete()))
+    }
+}
+impl<H> StrictDecode for StrictHash<H>
+where H: Digest + OutputSizeUser<OutputSize = U32> + StrictHashName
+{
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| r.read_field().map(Self::from_byte_array))
+    }
+}
+impl<H> StrictEncodeSize for StrictHash<H>
+where H: Digest + OutputSizeUser<OutputSize = U32> + StrictHashName
+{
+    fn encoded_size(&self) -> usize { Self::ENCODED_LEN }
+}
+impl<H> StrictEncodedLen for StrictHash<H>
+where H: Digest + OutputSizeUser<OutputSize = U32> + StrictHashName
+{
+    const ENCODED_LEN: usize = 32;
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(unused_imports)]
+
+    use super::*;
+    use crate::test::encoding;
+
+    #[test]
+    #[cfg(feature = "sha2")]
+    fn sha256() {
+        let hash = Sha256Hash::digest(b"");
+        let hex = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        assert_eq!(hash.to_string(), hex);
+        assert_eq!(Sha256Hash::from_str(hex), Ok(hash));
+        encoding(&hash, hash.to_byte_array());
+        assert_eq!(Sha256Hash::default().to_byte_array(), [0u8; 32]);
+        assert_eq!(Sha256Hash::strict_name(), Some(tn!("Sha256Hash")));
+        assert_eq!(Sha256Hash::ENCODED_LEN, 32);
+    }
+
+    #[test]
+    #[cfg(feature = "blake3")]
+    fn blake3() {
+        let hash = Blake3Hash::digest(b"");
+        let hex = "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262";
+        assert_eq!(hash.to_string(), hex);
+        assert_eq!(Blake3Hash::from_str(hex), Ok(hash));
+        encoding(&hash, hash.to_byte_array());
+        assert_eq!(Blake3Hash::strict_name(), Some(tn!("Blake3Hash")));
+    }
+}
@@ -0,0 +1,180 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fixed-size byte strings which are encoded as their hex representation.
+
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use crate::stl::AsciiSym;
+use crate::{
+    DecodeError, StrictDecode, StrictDumb, StrictEncode, StrictEncodeSize, StrictEncodedLen,
+    StrictType, TypedRead, TypedWrite, LIB_NAME_STD,
+};
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Errors parsing [`HexStr`] from a string.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum HexStrError {
+    /// hex string has {0} characters instead of {1}
+    WrongLength(usize, usize),
+
+    /// hex string contains invalid character {0:?}
+    InvalidChar(char),
+}
+
+/// `BYTES` bytes which are strict-encoded as `BYTES * 2` uppercase ASCII hex
+/// digits, without a length prefix.
+///
+/// Decoding accepts only uppercase digits, such that each value has a single
+/// encoding. Unlike that, [`FromStr`] accepts both cases.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct HexStr<const BYTES: usize>([u8; BYTES]);
+
+impl<const BYTES: usize> HexStr<BYTES> {
+    // Evaluated at compile time for each used `BYTES`, rejecting values whose
+    // digits can't be registered as a strict array.
+    const DIGITS: u16 = {
+        assert!(BYTES * 2 <= u16::MAX as usize, "HexStr digits must fit into u16");
+        (BYTES * 2) as u16
+    };
+}
+
+impl<const BYTES: usize> From<[u8; BYTES]> for HexStr<BYTES> {
+    fn from(bytes: [u8; BYTES]) -> Self { HexStr(bytes) }
+}
+
+impl<const BYTES: usize> Deref for HexStr<BYTES> {
+    type Target = [u8; BYTES];
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl<const BYTES: usize> AsRef<[u8]> for HexStr<BYTES> {
+    fn as_ref(&self) -> &[u8] { &self.0 }
+}
+
+impl<const BYTES: usize> Display for HexStr<BYTES> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02X}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<const BYTES: usize> FromStr for HexStr<BYTES> {
+    type Err = HexStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != BYTES * 2 {
+            return Err(HexStrError::WrongLength(s.chars().count(), BYTES * 2));
+        }
+        let mut bytes = [0u8; BYTES];
+        let mut chars = s.chars();
+        for byte in &mut bytes {
+            for _ in 0..2 {
+                let c = chars.next().expect("length is checked above");
+                let digit = c.to_digit(16).ok_or(HexStrError::InvalidChar(c))?;
+                *byte = (*byte << 4) | digit as u8;
+            }
+        }
+        Ok(HexStr(bytes))
+    }
+}
+
+impl<const BYTES: usize> StrictType for HexStr<BYTES> {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+}
+impl<const BYTES: usize> StrictDumb for HexStr<BYTES> {
+    fn strict_dumb() -> Self { HexStr([0; BYTES]) }
+}
+impl<const BYTES: usize> StrictEncode for HexStr<BYTES> {
+    fn strict_encode<W: TypedWrite>(&self, mut writer: W) -> io::Result<W> {
+        for byte in self.0 {
+            let digits = [HEX_DIGITS[(byte >> 4) as usize], HEX_DIGITS[(byte & 0x0F) as usize]];
+            writer = unsafe { writer._write_raw_array(digits)? };
+        }
+        Ok(unsafe { writer.register_array(&AsciiSym::strict_dumb(), Self::DIGITS) })
+    }
+}
+impl<const BYTES: usize> StrictDecode for HexStr<BYTES> {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let digit = |c: u8| {
+            HEX_DIGITS
+                .iter()
+                .position(|d| *d == c)
+                .map(|pos| pos as u8)
+                .ok_or_else(|| {
+                    DecodeError::DataIntegrityError(format!(
+                        "byte {c:#04x} is not an uppercase hex digit"
+                    ))
+                })
+        };
+        let _ = Self::DIGITS;
+        let mut bytes = [0u8; BYTES];
+        for byte in &mut bytes {
+            let [hi, lo] = unsafe { reader._read_raw_array::<2>()? };
+            *byte = (digit(hi)? << 4) | digit(lo)?;
+        }
+        Ok(HexStr(bytes))
+    }
+}
+impl<const BYTES: usize> StrictEncodeSize for HexStr<BYTES> {
+    fn encoded_size(&self) -> usize { Self::ENCODED_LEN }
+}
+impl<const BYTES: usize> StrictEncodedLen for HexStr<BYTES> {
+    const ENCODED_LEN: usize = BYTES * 2;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::encoding;
+    use crate::StrictReader;
+
+    #[test]
+    fn display_from_str() {
+        let hex = HexStr::from([0xDE, 0xAD, 0xBE, 0xEF]);
+        assert_eq!(hex.to_string(), "DEADBEEF");
+        assert_eq!(HexStr::from_str("deadBEEF"), Ok(hex));
+        assert_eq!(HexStr::<4>::from_str("DEAD"), Err(HexStrError::WrongLength(4, 8)));
+        assert_eq!(HexStr::<2>::from_str("DEAG"), Err(HexStrError::InvalidChar('G')));
+    }
+
+    #[test]
+    fn encoding_hex() {
+        encoding(&HexStr::from([0xDE, 0xAD, 0xBE, 0xEF]), b"DEADBEEF");
+        encoding(&HexStr::from([]), b"");
+        assert_eq!(HexStr::<4>::ENCODED_LEN, 8);
+        assert_eq!(HexStr::<4>::strict_name(), Some(tn!("HexStr4")));
+        assert_ne!(HexStr::<4>::strict_name(), HexStr::<32>::strict_name());
+
+        let mut reader = StrictReader::in_memory(b"dead".to_vec(), 4);
+        assert!(matches!(
+            HexStr::<2>::strict_decode(&mut reader),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+    }
+}
@@ -0,0 +1,175 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strict encoding for HTTP headers from `http` crate.
+
+use std::io;
+
+use amplify::confinement::Confined;
+use http::{HeaderMap, HeaderName, HeaderValue};
+
+use crate::{DecodeError, StrictDecode, StrictEncode, StrictType, TypedRead, TypedWrite};
+
+type NameString = Confined<String, 0, 0xFF>;
+type ValueBytes = Confined<Vec<u8>, 0, { u16::MAX as usize }>;
+type HeaderList = Confined<Vec<(NameString, ValueBytes)>, 0, { u16::MAX as usize }>;
+
+fn invalid_input(err: impl std::error::Error + Send + Sync + 'static) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, err)
+}
+
+fn decode_value(bytes: ValueBytes) -> Result<HeaderValue, DecodeError> {
+    HeaderValue::from_bytes(bytes.as_slice())
+        .map_err(|err| DecodeError::DataIntegrityError(format!("invalid HTTP header value: {err}")))
+}
+
+/// [`HeaderValue`] is encoded as a byte string of up to 2^16-1 bytes.
+impl StrictType for HeaderValue {
+    const STRICT_LIB_NAME: &'static str = ValueBytes::STRICT_LIB_NAME;
+}
+impl StrictEncode for HeaderValue {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        ValueBytes::try_from(self.as_bytes().to_vec())
+            .map_err(invalid_input)?
+            .strict_encode(writer)
+    }
+}
+impl StrictDecode for HeaderValue {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        decode_value(ValueBytes::strict_decode(reader)?)
+    }
+}
+
+/// [`HeaderMap`] is encoded as a list of up to 2^16-1 name-value pairs grouped
+/// by name: all values of a multi-value header follow each other in the order
+/// they were added, and the groups follow in the map iteration order. Names are
+/// lowercase strings of up to 255 bytes.
+///
+/// Decoding accepts only this form, failing on names which are not lowercase
+/// and on names which reappear after a group with another name.
+impl StrictType for HeaderMap {
+    const STRICT_LIB_NAME: &'static str = HeaderList::STRICT_LIB_NAME;
+}
+impl StrictEncode for HeaderMap {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        let mut list = Vec::with_capacity(self.len());
+        for (name, value) in self {
+            let name = NameString::try_from(name.as_str().to_owned()).map_err(invalid_input)?;
+            let value = ValueBytes::try_from(value.as_bytes().to_vec()).map_err(invalid_input)?;
+            list.push((name, value));
+        }
+        HeaderList::try_from(list)
+            .map_err(invalid_input)?
+            .strict_encode(writer)
+    }
+}
+impl StrictDecode for HeaderMap {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let list = HeaderList::strict_decode(reader)?;
+        let mut map = HeaderMap::with_capacity(list.len());
+        let mut last = None::<HeaderName>;
+        for (name, value) in list {
+            let header = HeaderName::from_bytes(name.as_bytes()).map_err(|err| {
+                DecodeError::DataIntegrityError(format!("invalid HTTP header name `{name}`: {err}"))
+            })?;
+            if header.as_str() != name.as_str() {
+                return Err(DecodeError::DataIntegrityError(format!(
+                    "HTTP header name `{name}` is not lowercase"
+                )));
+            }
+            if last.as_ref() != Some(&header) && map.contains_key(&header) {
+                return Err(DecodeError::DataIntegrityError(format!(
+                    "values of HTTP header `{name}` are not grouped together"
+                )));
+            }
+            map.append(&header, decode_value(value)?);
+            last = Some(header);
+        }
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use http::header::{CONTENT_TYPE, SET_COOKIE};
+
+    use super::*;
+    use crate::test::{encoding, encoding_roundtrip};
+    use crate::StrictReader;
+
+    #[test]
+    fn header_value() { encoding(&HeaderValue::from_static("gzip"), b"\x04\x00gzip"); }
+
+    #[test]
+    fn header_map() {
+        let mut map = HeaderMap::new();
+        map.insert(CONTENT_TYPE, HeaderValue::from_static("text/html"));
+        map.append(SET_COOKIE, HeaderValue::from_static("a=1"));
+        map.append(SET_COOKIE, HeaderValue::from_static("b=2"));
+        encoding(
+            &map,
+            b"\x03\x00\
+              \x0Ccontent-type\x09\x00text/html\
+              \x0Aset-cookie\x03\x00a=1\
+              \x0Aset-cookie\x03\x00b=2",
+        );
+
+        map.insert("x-request-id", HeaderValue::from_static("f0e1d2c3"));
+        map.append("accept", HeaderValue::from_static("text/html"));
+        map.append("accept", HeaderValue::from_static("application/json;q=0.9"));
+        encoding_roundtrip(&map);
+    }
+
+    #[test]
+    fn invalid() {
+        let mut reader = StrictReader::in_memory(b"\x01\x00\x03a b\x01\x00x".to_vec(), 0xFF);
+        assert!(matches!(
+            HeaderMap::strict_decode(&mut reader),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+        let mut reader = StrictReader::in_memory(b"\x02\x00\r\n".to_vec(), 0xFF);
+        assert!(matches!(
+            HeaderValue::strict_decode(&mut reader),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+    }
+
+    #[test]
+    fn non_lowercase_name() {
+        let mut reader = StrictReader::in_memory(b"\x01\x00\x06Accept\x01\x00*".to_vec(), 0xFF);
+        assert!(matches!(
+            HeaderMap::strict_decode(&mut reader),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+    }
+
+    #[test]
+    fn ungrouped_names() {
+        let mut reader = StrictReader::in_memory(
+            b"\x03\x00\x01a\x01\x00x\x01b\x01\x00y\x01a\x01\x00z".to_vec(),
+            0xFF,
+        );
+        assert!(matches!(
+            HeaderMap::strict_decode(&mut reader),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+    }
+}
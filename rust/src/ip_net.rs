@@ -0,0 +1,211 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strict encoding for IP network prefixes from `ipnet` crate.
+
+use std::io;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use ipnet::{IpNet, Ipv4Net, Ipv6Net};
+
+use crate::{
+    DecodeError, StrictDecode, StrictDumb, StrictEncode, StrictEncodeSize, StrictEncodedLen,
+    StrictType, TypeName, TypedRead, TypedWrite, LIB_NAME_STD,
+};
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STD, rename = "Ipv4Net", crate = crate)]
+struct Ipv4NetRepr {
+    addr: [u8; 4],
+    prefix_len: u8,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STD, rename = "Ipv6Net", crate = crate)]
+struct Ipv6NetRepr {
+    addr: [u8; 16],
+    prefix_len: u8,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STD, tags = order, rename = "IpNet", crate = crate)]
+enum IpNetRepr {
+    V4(Ipv4Net),
+    V6(Ipv6Net),
+}
+
+impl StrictDumb for IpNetRepr {
+    fn strict_dumb() -> Self { IpNetRepr::V4(Ipv4Net::strict_dumb()) }
+}
+
+/// [`Ipv4Net`] is encoded as 4 bytes of the address (which may have host bits
+/// set) followed by the prefix length byte. Decoding fails if the prefix
+/// length exceeds 32.
+impl StrictType for Ipv4Net {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+    fn strict_name() -> Option<TypeName> { Ipv4NetRepr::strict_name() }
+}
+impl StrictEncode for Ipv4Net {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        Ipv4NetRepr {
+            addr: self.addr().octets(),
+            prefix_len: self.prefix_len(),
+        }
+        .strict_encode(writer)
+    }
+}
+impl StrictDecode for Ipv4Net {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let repr = Ipv4NetRepr::strict_decode(reader)?;
+        Ipv4Net::new(Ipv4Addr::from(repr.addr), repr.prefix_len).map_err(|_| {
+            DecodeError::DataIntegrityError(format!(
+                "invalid IPv4 network prefix length {}",
+                repr.prefix_len
+            ))
+        })
+    }
+}
+impl StrictEncodeSize for Ipv4Net {
+    fn encoded_size(&self) -> usize { Self::ENCODED_LEN }
+}
+impl StrictEncodedLen for Ipv4Net {
+    const ENCODED_LEN: usize = 5;
+}
+
+/// [`Ipv6Net`] is encoded as 16 bytes of the address (which may have host
+/// bits set) followed by the prefix length byte. Decoding fails if the prefix
+/// length exceeds 128.
+impl StrictType for Ipv6Net {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+    fn strict_name() -> Option<TypeName> { Ipv6NetRepr::strict_name() }
+}
+impl StrictEncode for Ipv6Net {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        Ipv6NetRepr {
+            addr: self.addr().octets(),
+            prefix_len: self.prefix_len(),
+        }
+        .strict_encode(writer)
+    }
+}
+impl StrictDecode for Ipv6Net {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let repr = Ipv6NetRepr::strict_decode(reader)?;
+        Ipv6Net::new(Ipv6Addr::from(repr.addr), repr.prefix_len).map_err(|_| {
+            DecodeError::DataIntegrityError(format!(
+                "invalid IPv6 network prefix length {}",
+                repr.prefix_len
+            ))
+        })
+    }
+}
+impl StrictEncodeSize for Ipv6Net {
+    fn encoded_size(&self) -> usize { Self::ENCODED_LEN }
+}
+impl StrictEncodedLen for Ipv6Net {
+    const ENCODED_LEN: usize = 17;
+}
+
+/// [`IpNet`] is encoded as a tag (`0` for IPv4 and `1` for IPv6) followed by
+/// the encoding of the network prefix.
+impl StrictType for IpNet {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+    fn strict_name() -> Option<TypeName> { IpNetRepr::strict_name() }
+}
+impl StrictEncode for IpNet {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        match *self {
+            IpNet::V4(net) => IpNetRepr::V4(net),
+            IpNet::V6(net) => IpNetRepr::V6(net),
+        }
+        .strict_encode(writer)
+    }
+}
+impl StrictDecode for IpNet {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        Ok(match IpNetRepr::strict_decode(reader)? {
+            IpNetRepr::V4(net) => IpNet::V4(net),
+            IpNetRepr::V6(net) => IpNet::V6(net),
+        })
+    }
+}
+impl StrictEncodeSize for IpNet {
+    fn encoded_size(&self) -> usize {
+        1 + match self {
+            IpNet::V4(net) => net.encoded_size(),
+            IpNet::V6(net) => net.encoded_size(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::{encode, encoding};
+    use crate::StrictReader;
+
+    #[test]
+    fn ipv4_net() {
+        let net: Ipv4Net = "192.168.1.7/24".parse().unwrap();
+        encoding(&net, [192, 168, 1, 7, 24]);
+        assert_eq!(encode(&net).len(), Ipv4Net::ENCODED_LEN);
+
+        let mut reader = StrictReader::in_memory(vec![10, 0, 0, 0, 33], 5);
+        assert!(matches!(
+            Ipv4Net::strict_decode(&mut reader),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+    }
+
+    #[test]
+    fn ipv6_net() {
+        let net: Ipv6Net = "2001:db8::/32".parse().unwrap();
+        let mut data = vec![0x20, 0x01, 0x0d, 0xb8];
+        data.extend([0; 12]);
+        data.push(32);
+        encoding(&net, data.clone());
+        assert_eq!(encode(&net).len(), Ipv6Net::ENCODED_LEN);
+
+        data[16] = 129;
+        let mut reader = StrictReader::in_memory(data, 17);
+        assert!(matches!(
+            Ipv6Net::strict_decode(&mut reader),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+    }
+
+    #[test]
+    fn ip_net() {
+        let v4: IpNet = "10.0.0.0/8".parse().unwrap();
+        encoding(&v4, [0, 10, 0, 0, 0, 8]);
+        assert_eq!(v4.encoded_size(), 6);
+
+        let v6: IpNet = "::1/128".parse().unwrap();
+        let mut data = vec![1];
+        data.extend([0; 15]);
+        data.extend([1, 128]);
+        encoding(&v6, data);
+        assert_eq!(v6.encoded_size(), 18);
+    }
+}
@@ -0,0 +1,153 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strict encoding for JSON attribute maps from `serde_json` crate.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+
+use amplify::confinement::Confined;
+use serde_json::Value;
+
+use crate::{DecodeError, StrictDecode, StrictEncode, StrictType, TypedRead, TypedWrite};
+
+/// Maximal length of an attribute key, in bytes.
+pub const ATTR_KEY_MAX_LEN: usize = 0xFF;
+/// Maximal length of a JSON-serialized attribute value, in bytes.
+pub const ATTR_VALUE_MAX_LEN: usize = u16::MAX as usize;
+
+type AttrKey = Confined<String, 0, ATTR_KEY_MAX_LEN>;
+type AttrValue = Confined<String, 0, ATTR_VALUE_MAX_LEN>;
+type AttrMap = Confined<BTreeMap<AttrKey, AttrValue>, 0, { u16::MAX as usize }>;
+
+/// Attribute map is encoded as a map of up to 2^16-1 entries, ordered by their
+/// keys. Each key is a non-empty string of at most [`ATTR_KEY_MAX_LEN`] bytes,
+/// and each value is its compact JSON serialization of at most
+/// [`ATTR_VALUE_MAX_LEN`] bytes. Decoding accepts only values serialized
+/// exactly the way the encoder does it, such that each map has a single
+/// encoding.
+///
+/// Object keys are serialized ordered alphabetically. Enabling `preserve_order`
+/// feature of `serde_json` anywhere in the dependency graph makes them keep the
+/// insertion order instead, changing the encoding of the same map.
+impl StrictType for HashMap<String, Value> {
+    const STRICT_LIB_NAME: &'static str = AttrMap::STRICT_LIB_NAME;
+    fn strict_name() -> Option<crate::TypeName> { None }
+}
+impl StrictEncode for HashMap<String, Value> {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        let invalid = |err| io::Error::new(io::ErrorKind::InvalidInput, err);
+        let mut map = BTreeMap::new();
+        for (key, value) in self {
+            if key.is_empty() {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "empty attribute key"));
+            }
+            let key = AttrKey::try_from(key.clone()).map_err(invalid)?;
+            let json = serde_json::to_string(value).map_err(io::Error::from)?;
+            let value = AttrValue::try_from(json).map_err(invalid)?;
+            map.insert(key, value);
+        }
+        AttrMap::try_from(map)
+            .map_err(invalid)?
+            .strict_encode(writer)
+    }
+}
+impl StrictDecode for HashMap<String, Value> {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let len = unsafe { reader._read_raw_len::<{ u16::MAX as usize }>()? };
+        let mut map = HashMap::with_capacity(len);
+        let mut last = None::<AttrKey>;
+        for _ in 0..len {
+            let key = AttrKey::strict_decode(reader)?;
+            let json = AttrValue::strict_decode(reader)?;
+            if key.is_empty() {
+                return Err(DecodeError::DataIntegrityError(s!("empty attribute key")));
+            }
+            match &last {
+                Some(last) if last == &key => {
+                    return Err(DecodeError::DuplicateAttributeKey(key.into_inner()));
+                }
+                Some(last) if last > &key => return Err(DecodeError::BrokenMapOrder),
+                _ => {}
+            }
+            let value = serde_json::from_str(json.as_str()).map_err(|err| {
+                DecodeError::DataIntegrityError(format!(
+                    "invalid JSON value for attribute `{key}`: {err}"
+                ))
+            })?;
+            if serde_json::to_string(&value).ok().as_deref() != Some(json.as_str()) {
+                return Err(DecodeError::DataIntegrityError(format!(
+                    "JSON value for attribute `{key}` is not in its canonical form"
+                )));
+            }
+            map.insert(key.to_string(), value);
+            last = Some(key);
+        }
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+    use crate::test::encoding;
+    use crate::StrictReader;
+
+    #[test]
+    fn attr_map() {
+        let mut map = HashMap::new();
+        map.insert("b".to_owned(), json!({"x": [1, 2]}));
+        map.insert("a".to_owned(), json!(null));
+        encoding(&map, b"\x02\x00\x01a\x04\x00null\x01b\x0B\x00{\"x\":[1,2]}");
+    }
+
+    #[test]
+    fn attr_map_invalid() {
+        let decode = |data: &[u8]| {
+            let mut reader = StrictReader::in_memory(data.to_vec(), 0xFF);
+            HashMap::<String, Value>::strict_decode(&mut reader)
+        };
+        assert_eq!(
+            decode(b"\x02\x00\x01a\x01\x001\x01a\x01\x002"),
+            Err(DecodeError::DuplicateAttributeKey("a".to_owned()))
+        );
+        assert_eq!(
+            decode(b"\x02\x00\x01b\x01\x001\x01a\x01\x002"),
+            Err(DecodeError::BrokenMapOrder)
+        );
+        assert!(matches!(
+            decode(b"\x01\x00\x00\x01\x001"),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+        assert!(matches!(
+            decode(b"\x01\x00\x01a\x01\x00{"),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+        for json in [&b" 1"[..], b"1e0", b"1.50", b"{\"y\":1,\"x\":2}", b"[1, 2]"] {
+            let mut data = b"\x01\x00\x01a".to_vec();
+            data.extend((json.len() as u16).to_le_bytes());
+            data.extend(json);
+            assert!(matches!(decode(&data), Err(DecodeError::DataIntegrityError(_))));
+        }
+    }
+}
@@ -0,0 +1,142 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strict encoding for keys and signatures from pure-Rust `k256` crate.
+
+use std::io;
+
+use amplify::confinement::Confined;
+use k256::ecdsa::Signature;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::{PublicKey, SecretKey};
+
+use crate::{
+    DecodeError, StrictDecode, StrictEncode, StrictEncodeSize, StrictEncodedLen, StrictType,
+    TypeName, TypedRead, TypedWrite, LIB_NAME_STD,
+};
+
+/// Maximal length of a DER-encoded ECDSA signature accepted by the decoder of
+/// [`Signature`].
+const DER_MAX_LEN: usize = 73;
+
+// Public keys are always encoded in the compressed form, matching the encoding
+// of `secp256k1::PublicKey`.
+impl_fixed!(
+    PublicKey,
+    33,
+    "K256PublicKey",
+    "secp256k1 public key",
+    |key| <[u8; 33]>::try_from(key.to_encoded_point(true).as_bytes())
+        .expect("compressed public key is always 33 bytes long"),
+    |bytes| PublicKey::from_sec1_bytes(&bytes)
+);
+impl_fixed!(
+    SecretKey,
+    32,
+    "K256SecretKey",
+    "secp256k1 secret key",
+    |key| <[u8; 32]>::from(key.to_bytes()),
+    |bytes| SecretKey::from_slice(&bytes)
+);
+
+/// ECDSA signature is encoded in DER format prefixed with its length, which is
+/// the same encoding as used by `DerSignature` for `secp256k1` signatures.
+impl StrictType for Signature {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+    fn strict_name() -> Option<TypeName> { Some(tn!("K256DerSignature")) }
+}
+impl StrictEncode for Signature {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        Confined::<Vec<u8>, 0, DER_MAX_LEN>::try_from(self.to_der().as_bytes().to_vec())
+            .expect("DER-encoded signature never exceeds 73 bytes")
+            .strict_encode(writer)
+    }
+}
+impl StrictDecode for Signature {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let der = Confined::<Vec<u8>, 0, DER_MAX_LEN>::strict_decode(reader)?;
+        Signature::from_der(&der).map_err(|err| {
+            DecodeError::DataIntegrityError(format!("invalid DER ECDSA signature: {err}"))
+        })
+    }
+}
+impl StrictEncodeSize for Signature {
+    fn encoded_size(&self) -> usize { 1 + self.to_der().len() }
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::hex::FromHex;
+
+    use super::*;
+    use crate::test::{encode, encoding};
+    use crate::StrictReader;
+
+    // Generator point G, which is the public key for the secret key 1
+    const GENERATOR: &str = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    // Signature from the input of the first bitcoin transaction spending coins
+    // (f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16)
+    const DER_SIG: &str = "304402204e45e16932b8af514961a1d3a1a25fdf3f4f7732e9d624c6c61548ab5fb8cd41\
+                           0220181522ec8eca07de4860a4acdd12909d831cc56cbbac4622082221a8768d1d09";
+
+    fn decode_err<T: StrictDecode + std::fmt::Debug>(data: Vec<u8>) {
+        let len = data.len();
+        let mut reader = StrictReader::in_memory(data, len);
+        assert!(matches!(T::strict_decode(&mut reader), Err(DecodeError::DataIntegrityError(_))));
+    }
+
+    #[test]
+    fn keys() {
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        let secret = SecretKey::from_slice(&one).unwrap();
+        let public = secret.public_key();
+
+        encoding(&secret, one);
+        encoding(&public, <[u8; 33]>::from_hex(GENERATOR).unwrap());
+        assert_eq!(public.encoded_size(), encode(&public).len());
+
+        decode_err::<SecretKey>(vec![0u8; 32]);
+        decode_err::<PublicKey>(vec![0x05; 33]);
+        // Valid tag, but x coordinate is not on the curve
+        let mut off_curve = vec![0x02];
+        off_curve.extend([0x00; 31]);
+        off_curve.push(0x05);
+        decode_err::<PublicKey>(off_curve);
+
+        assert_eq!(PublicKey::strict_name(), Some(tn!("K256PublicKey")));
+        assert_eq!(SecretKey::strict_name(), Some(tn!("K256SecretKey")));
+    }
+
+    #[test]
+    fn signature() {
+        let der = Vec::<u8>::from_hex(DER_SIG).unwrap();
+        let sig = Signature::from_der(&der).unwrap();
+        let mut expected = vec![der.len() as u8];
+        expected.extend_from_slice(&der);
+        encoding(&sig, expected);
+        assert_eq!(sig.encoded_size(), encode(&sig).len());
+
+        decode_err::<Signature>(vec![4, 0x30, 0x02, 0x02, 0x01]);
+
+        assert_eq!(Signature::strict_name(), Some(tn!("K256DerSignature")));
+    }
+}
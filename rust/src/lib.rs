@@ -31,13 +31,18 @@
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
 #[cfg(feature = "derive")]
-pub use derive::{StrictDecode, StrictDumb, StrictEncode, StrictType};
+pub use derive::{
+    strict_interface, StrictDecode, StrictDumb, StrictEncode, StrictEncodeCompatTest,
+    StrictEncodeSize, StrictType,
+};
 #[cfg(not(feature = "derive"))]
 use derive::{StrictDecode, StrictDumb, StrictEncode, StrictType};
 #[cfg(feature = "derive")]
 pub use strict_encoding_derive as derive;
 #[cfg(not(feature = "derive"))]
 use strict_encoding_derive as derive;
+#[cfg(feature = "zeroize")]
+pub use zeroize;
 
 #[macro_use]
 extern crate amplify;
@@ -56,20 +61,92 @@ mod writer;
 mod util;
 mod primitives;
 mod embedded;
+mod nonce;
+mod size;
+mod time;
+mod process;
+mod hex_str;
+mod string_map;
+mod schema;
+#[doc(hidden)]
+pub mod wire_watch;
 pub mod stl;
+#[cfg(feature = "crossbeam")]
+mod crossbeam;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+#[cfg(any(feature = "once_cell", feature = "once_lock"))]
+mod once;
+#[cfg(feature = "digest")]
+mod hashes;
+#[cfg(feature = "mime")]
+mod mime_type;
+#[cfg(feature = "serde_json")]
+mod json;
+#[cfg(feature = "socket2")]
+mod sockaddr;
+#[cfg(feature = "http")]
+mod http_headers;
+#[cfg(feature = "tinystr")]
+mod tiny_str;
+#[cfg(feature = "ed25519")]
+mod ed25519;
+#[cfg(feature = "secp256k1")]
+mod secp;
+#[cfg(feature = "x25519")]
+mod x25519;
+#[cfg(feature = "bitvec")]
+mod bit_vec;
+#[cfg(feature = "bech32")]
+mod bech32_str;
+#[cfg(feature = "ipnet")]
+mod ip_net;
+#[cfg(feature = "k256")]
+mod k256_ecdsa;
+#[cfg(feature = "lightning_invoice")]
+mod bolt11;
 #[cfg(test)]
 pub(crate) mod test;
 
-pub use embedded::Byte;
-pub use error::{DecodeError, DeserializeError, SerializeError};
+#[cfg(feature = "bech32")]
+pub use bech32_str::{Bech32Encoded, Bech32Hrp};
+#[cfg(feature = "lightning_invoice")]
+pub use bolt11::BOLT11_INVOICE_MAX_LEN;
+pub use embedded::{BoundedMap, Byte};
+pub use error::{DecodeError, DeserializeError, EncodeError, SerializeError};
+#[cfg(feature = "blake3")]
+pub use hashes::Blake3Hash;
+#[cfg(feature = "sha2")]
+pub use hashes::Sha256Hash;
+#[cfg(feature = "digest")]
+pub use hashes::{StrictHash, StrictHashName};
+pub use hex_str::{HexStr, HexStrError};
 pub use ident::{FieldName, Ident, InvalidIdent, LibName, TypeName, VariantName};
+#[cfg(feature = "serde_json")]
+pub use json::{ATTR_KEY_MAX_LEN, ATTR_VALUE_MAX_LEN};
+#[cfg(feature = "mime")]
+pub use mime_type::MIME_MAX_LEN;
+pub use nonce::{NonceRegistry, StrictNonce, NONCE_REGISTRY_CAPACITY};
 pub use primitives::{constants, NumCls, NumInfo, NumSize, Primitive};
-pub use reader::StrictReader;
+pub use reader::{
+    read_bytes_exact, read_bytes_vec, strict_skip, strict_skip_fixed, StrictReadExt, StrictReader,
+};
+pub use schema::{schema_hash, SchemaHasher};
+#[cfg(feature = "secp256k1")]
+pub use secp::{DerSignature, DER_SIGNATURE_MAX_LEN};
+pub use size::{len_prefix_size, StrictEncodeSize, StrictEncodedLen};
 pub use stl::{Bool, U4};
+pub use time::ProcessEpoch;
 pub use traits::*;
 pub use types::*;
-pub use util::{Sizing, Variant};
-pub use writer::{SplitParent, StrictParent, StrictWriter, StructWriter, UnionWriter};
+pub use util::{HeaderField, Sizing, StrictMinMax, Variant};
+#[cfg(feature = "wire_watch")]
+pub use wire_watch::{WireDirection, WireEvent, WireWatcher};
+pub use writer::{
+    strict_encode_fixed, strict_encode_to_writer, strict_encode_transactional, SplitParent,
+    StrictParent, StrictWriteExt, StrictWriter, StructWriter, TeeWriter, TransactionalEncoder,
+    UnionWriter,
+};
 
 #[deprecated(since = "2.2.0", note = "use LIB_EMBEDDED")]
 pub const NO_LIB: &str = LIB_EMBEDDED;
@@ -145,3 +145,46 @@ macro_rules! libname {
         $crate::LibName::from($name)
     };
 }
+
+/// Implements strict encoding for a type which is encoded as a fixed-size
+/// byte array, converted with `$ser` on encoding and with fallible `$de` on
+/// decoding.
+#[cfg(any(feature = "secp256k1", feature = "k256"))]
+#[rustfmt::skip]
+macro_rules! impl_fixed {
+    (
+        $ty:ty, $len:literal, $tn:literal, $name:literal,
+        |$self:ident| $ser:expr,
+        |$bytes:ident| $de:expr
+    ) => {
+        impl $crate::StrictType for $ty {
+            const STRICT_LIB_NAME: &'static str = <[u8; $len]>::STRICT_LIB_NAME;
+            fn strict_name() -> Option<$crate::TypeName> { Some(tn!($tn)) }
+        }
+        impl $crate::StrictEncode for $ty {
+            fn strict_encode<W: $crate::TypedWrite>(&self, writer: W) -> ::std::io::Result<W> {
+                let $self = self;
+                $ser.strict_encode(writer)
+            }
+        }
+        impl $crate::StrictDecode for $ty {
+            fn strict_decode(
+                reader: &mut impl $crate::TypedRead,
+            ) -> Result<Self, $crate::DecodeError> {
+                let $bytes = <[u8; $len]>::strict_decode(reader)?;
+                $de.map_err(|err| {
+                    $crate::DecodeError::DataIntegrityError(format!(
+                        concat!("invalid ", $name, ": {}"),
+                        err
+                    ))
+                })
+            }
+        }
+        impl $crate::StrictEncodeSize for $ty {
+            fn encoded_size(&self) -> usize { Self::ENCODED_LEN }
+        }
+        impl $crate::StrictEncodedLen for $ty {
+            const ENCODED_LEN: usize = $len;
+        }
+    };
+}
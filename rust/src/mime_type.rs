@@ -0,0 +1,74 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strict encoding for MIME types from `mime` crate.
+
+use std::io;
+use std::str::FromStr;
+
+use amplify::confinement::Confined;
+use mime::Mime;
+
+use crate::{DecodeError, StrictDecode, StrictEncode, StrictType, TypedRead, TypedWrite};
+
+/// Maximal length of a strict-encoded MIME type string, in bytes.
+pub const MIME_MAX_LEN: usize = 256;
+
+type MimeString = Confined<String, 0, MIME_MAX_LEN>;
+
+/// [`Mime`] is encoded as a string of at most [`MIME_MAX_LEN`] bytes.
+impl StrictType for Mime {
+    const STRICT_LIB_NAME: &'static str = MimeString::STRICT_LIB_NAME;
+}
+impl StrictEncode for Mime {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        MimeString::try_from(self.to_string())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?
+            .strict_encode(writer)
+    }
+}
+impl StrictDecode for Mime {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let s = MimeString::strict_decode(reader)?;
+        Mime::from_str(s.as_str()).map_err(|err| {
+            DecodeError::DataIntegrityError(format!("invalid MIME type `{s}`: {err}"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::encoding;
+    use crate::StrictReader;
+
+    #[test]
+    fn mime() {
+        encoding(&mime::APPLICATION_JSON, b"\x10\x00application/json");
+        encoding(&mime::IMAGE_PNG, b"\x09\x00image/png");
+
+        let mut reader = StrictReader::in_memory(b"\x05\x00image".to_vec(), 7);
+        assert!(matches!(
+            Mime::strict_decode(&mut reader),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+    }
+}
@@ -0,0 +1,197 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Single-use nonces with replay detection performed during decoding.
+
+use std::collections::BTreeSet;
+use std::mem;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use amplify::Wrapper;
+
+use crate::{DecodeError, StrictDecode, StrictEncode, StrictType, TypedRead, LIB_NAME_STD};
+
+/// 128-bit nonce which can be decoded only once per [`NonceRegistry`].
+///
+/// Decoding with [`StrictDecode`] checks the nonce against the process-wide
+/// [`NonceRegistry::global`] registry; use [`StrictNonce::decode_checked`] to
+/// provide a custom one.
+#[derive(Wrapper, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default, From)]
+#[wrapper(Deref, BorrowSlice)]
+#[derive(StrictType, StrictEncode)]
+#[strict_type(lib = LIB_NAME_STD, crate = crate)]
+pub struct StrictNonce([u8; 16]);
+
+impl StrictNonce {
+    /// Decodes nonce and registers it in the provided `registry`, failing with
+    /// [`DecodeError::ReplayedNonce`] if the nonce was already seen.
+    pub fn decode_checked(
+        reader: &mut impl TypedRead,
+        registry: &NonceRegistry,
+    ) -> Result<Self, DecodeError> {
+        let nonce = reader.read_newtype::<Self>()?;
+        if !registry.register(nonce) {
+            return Err(DecodeError::ReplayedNonce);
+        }
+        Ok(nonce)
+    }
+}
+
+impl StrictDecode for StrictNonce {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        Self::decode_checked(reader, NonceRegistry::global())
+    }
+}
+
+/// Default number of the most recent nonces kept by a [`NonceRegistry`].
+pub const NONCE_REGISTRY_CAPACITY: usize = 0x10000;
+
+/// Registry of the most recent nonces which were already decoded.
+///
+/// The registry keeps at most [`NonceRegistry::capacity`] nonces: once it is
+/// full, registering a new nonce evicts the oldest one, which may then be
+/// replayed. The capacity must be large enough to cover the time window in
+/// which replays are possible for the protocol.
+#[derive(Debug)]
+pub struct NonceRegistry(Mutex<NonceLog>);
+
+#[derive(Debug)]
+struct NonceLog {
+    capacity: usize,
+    seen: BTreeSet<StrictNonce>,
+    // Ring buffer of the registered nonces in the order of their registration
+    order: Vec<StrictNonce>,
+    oldest: usize,
+}
+
+static GLOBAL_REGISTRY: NonceRegistry = NonceRegistry::new();
+
+impl Default for NonceRegistry {
+    fn default() -> Self { NonceRegistry::new() }
+}
+
+impl NonceRegistry {
+    /// Constructs empty registry keeping [`NONCE_REGISTRY_CAPACITY`] nonces.
+    pub const fn new() -> Self { NonceRegistry::with_capacity(NONCE_REGISTRY_CAPACITY) }
+
+    /// Constructs empty registry keeping up to `capacity` most recent nonces.
+    ///
+    /// # Panics
+    ///
+    /// If the capacity is zero.
+    pub const fn with_capacity(capacity: usize) -> Self {
+        assert!(capacity > 0, "nonce registry capacity must be non-zero");
+        NonceRegistry(Mutex::new(NonceLog {
+            capacity,
+            seen: BTreeSet::new(),
+            order: Vec::new(),
+            oldest: 0,
+        }))
+    }
+
+    /// Returns process-wide registry used by [`StrictNonce`] decoder.
+    pub fn global() -> &'static NonceRegistry { &GLOBAL_REGISTRY }
+
+    /// Returns maximal number of nonces kept by the registry.
+    pub fn capacity(&self) -> usize { self.log().capacity }
+
+    /// Adds nonce to the registry, evicting the oldest one if the registry is
+    /// full. Returns `false` if the nonce was already present.
+    pub fn register(&self, nonce: StrictNonce) -> bool {
+        let mut log = self.log();
+        if !log.seen.insert(nonce) {
+            return false;
+        }
+        if log.order.len() < log.capacity {
+            log.order.push(nonce);
+        } else {
+            let oldest = log.oldest;
+            let evicted = mem::replace(&mut log.order[oldest], nonce);
+            log.seen.remove(&evicted);
+            log.oldest = (oldest + 1) % log.capacity;
+        }
+        true
+    }
+
+    /// Checks whether the nonce is present in the registry.
+    pub fn contains(&self, nonce: &StrictNonce) -> bool { self.log().seen.contains(nonce) }
+
+    /// Returns number of registered nonces.
+    pub fn len(&self) -> usize { self.log().seen.len() }
+
+    /// Detects whether the registry has no nonces.
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Removes all nonces from the registry.
+    pub fn clear(&self) {
+        let mut log = self.log();
+        log.seen.clear();
+        log.order.clear();
+        log.oldest = 0;
+    }
+
+    fn log(&self) -> MutexGuard<'_, NonceLog> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::encode;
+    use crate::StrictReader;
+
+    #[test]
+    fn replay() {
+        let nonce = StrictNonce::from([0xA5; 16]);
+        let data = encode(&nonce);
+        assert_eq!(data, [0xA5; 16]);
+
+        let registry = NonceRegistry::new();
+        let mut reader = StrictReader::in_memory(data.clone(), 16);
+        assert_eq!(StrictNonce::decode_checked(&mut reader, &registry), Ok(nonce));
+        assert!(registry.contains(&nonce));
+
+        let mut reader = StrictReader::in_memory(data, 16);
+        assert_eq!(
+            StrictNonce::decode_checked(&mut reader, &registry),
+            Err(DecodeError::ReplayedNonce)
+        );
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn eviction() {
+        let registry = NonceRegistry::with_capacity(2);
+        let nonces = [[1u8; 16], [2; 16], [3; 16]].map(StrictNonce::from);
+        for nonce in nonces {
+            assert!(registry.register(nonce));
+        }
+        assert_eq!(registry.len(), 2);
+        assert!(!registry.contains(&nonces[0]));
+        assert!(!registry.register(nonces[2]));
+
+        // The evicted nonce is accepted again and evicts the next oldest one
+        assert!(registry.register(nonces[0]));
+        assert!(!registry.contains(&nonces[1]));
+        assert!(registry.contains(&nonces[2]));
+    }
+}
@@ -0,0 +1,106 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strict encoding for single-initialization cells, which share the strict
+//! type with [`Option`]: an uninitialized cell is encoded as `none`, and an
+//! initialized one – as `some` with the cell value.
+
+use std::io;
+use std::marker::PhantomData;
+
+use crate::embedded::write_option;
+use crate::{
+    DecodeError, SchemaHasher, StrictDecode, StrictDumb, StrictEncode, StrictType, TypeName,
+    TypedRead, TypedWrite, LIB_EMBEDDED,
+};
+
+macro_rules! encode_once {
+    ($ty:ident) => {
+        impl<T> StrictType for $ty<T>
+        where T: StrictType
+        {
+            const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+            fn strict_name() -> Option<TypeName> { None }
+            fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) {
+                Option::<T>::strict_schema(hasher, PhantomData)
+            }
+        }
+        impl<T: StrictEncode + StrictDumb> StrictEncode for $ty<T> {
+            fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+                write_option(writer, self.get())
+            }
+        }
+        impl<T: StrictDecode> StrictDecode for $ty<T> {
+            fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+                Ok(match Option::<T>::strict_decode(reader)? {
+                    None => $ty::new(),
+                    Some(val) => $ty::from(val),
+                })
+            }
+        }
+    };
+}
+
+#[cfg(feature = "once_cell")]
+mod once_cell_impl {
+    use once_cell::sync::OnceCell;
+    use once_cell::unsync::OnceCell as UnsyncOnceCell;
+
+    use super::*;
+
+    encode_once!(OnceCell);
+    encode_once!(UnsyncOnceCell);
+}
+
+#[cfg(feature = "once_lock")]
+mod once_lock_impl {
+    use std::sync::OnceLock;
+
+    use super::*;
+
+    encode_once!(OnceLock);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test::encoding;
+
+    #[test]
+    #[cfg(feature = "once_cell")]
+    fn once_cell() {
+        use once_cell::sync::OnceCell;
+        use once_cell::unsync::OnceCell as UnsyncOnceCell;
+
+        encoding(&OnceCell::<u8>::new(), [0x00]);
+        encoding(&OnceCell::from(0xA5u8), [0x01, 0xA5]);
+        encoding(&UnsyncOnceCell::<u8>::new(), [0x00]);
+        encoding(&UnsyncOnceCell::from(0xA5u8), [0x01, 0xA5]);
+    }
+
+    #[test]
+    #[cfg(feature = "once_lock")]
+    fn once_lock() {
+        use std::sync::OnceLock;
+
+        encoding(&OnceLock::<u8>::new(), [0x00]);
+        encoding(&OnceLock::from(0xA5u8), [0x01, 0xA5]);
+    }
+}
@@ -0,0 +1,180 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strict encoding for process exit statuses.
+
+use std::io;
+use std::process::ExitStatus;
+
+use crate::{
+    DecodeError, StrictDecode, StrictDumb, StrictEncode, StrictType, TypeName, TypedRead,
+    TypedWrite, LIB_NAME_STD,
+};
+
+/// Strict type representation of [`ExitStatus`]: a tag followed by the
+/// process exit code or the number of the signal which terminated the
+/// process.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STD, tags = order, rename = "ExitStatus", crate = crate)]
+enum ExitStatusRepr {
+    Exited(i32),
+    Signaled(i32),
+    /// Process was neither exited nor terminated by a signal (i.e. it was
+    /// stopped or continued); always carries zero.
+    Unknown(i32),
+}
+
+impl StrictDumb for ExitStatusRepr {
+    fn strict_dumb() -> Self { ExitStatusRepr::Exited(0) }
+}
+
+impl From<&ExitStatus> for ExitStatusRepr {
+    fn from(status: &ExitStatus) -> Self {
+        if let Some(code) = status.code() {
+            return ExitStatusRepr::Exited(code);
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            if let Some(signal) = status.signal() {
+                return ExitStatusRepr::Signaled(signal);
+            }
+        }
+        ExitStatusRepr::Unknown(0)
+    }
+}
+
+impl TryFrom<ExitStatusRepr> for ExitStatus {
+    type Error = DecodeError;
+
+    fn try_from(repr: ExitStatusRepr) -> Result<Self, Self::Error> {
+        Ok(match repr {
+            #[cfg(unix)]
+            ExitStatusRepr::Exited(code) => {
+                use std::os::unix::process::ExitStatusExt;
+                if !(0..=0xFF).contains(&code) {
+                    return Err(DecodeError::DataIntegrityError(format!(
+                        "process exit code {code} is out of 0..=255 range"
+                    )));
+                }
+                ExitStatus::from_raw(code << 8)
+            }
+            #[cfg(unix)]
+            ExitStatusRepr::Signaled(signal) => {
+                use std::os::unix::process::ExitStatusExt;
+                // Zero and 0x7F in the lower 7 bits of a raw status mean
+                // exited and stopped/continued processes, respectively.
+                if !(1..=0x7E).contains(&signal) {
+                    return Err(DecodeError::DataIntegrityError(format!(
+                        "process termination signal {signal} is out of 1..=126 range"
+                    )));
+                }
+                ExitStatus::from_raw(signal)
+            }
+            #[cfg(windows)]
+            ExitStatusRepr::Exited(code) => {
+                use std::os::windows::process::ExitStatusExt;
+                ExitStatus::from_raw(code as u32)
+            }
+            ExitStatusRepr::Unknown(_) => {
+                return Err(DecodeError::DataIntegrityError(s!("exit status of a process which \
+                                                               is neither exited nor signaled \
+                                                               can't be reconstructed")));
+            }
+            #[allow(unreachable_patterns)]
+            _ => {
+                return Err(DecodeError::DataIntegrityError(s!(
+                    "exit status is not supported on this platform"
+                )));
+            }
+        })
+    }
+}
+
+/// [`ExitStatus`] is encoded as `Exited(i32)`, `Signaled(i32)` or
+/// `Unknown(i32)` variant with tags 0, 1 and 2.
+///
+/// On Unix, decoded statuses are reconstructed from the exit code or signal
+/// number only, such that extra information (like a core dump flag) is lost.
+/// Exit codes outside of `0..=255` and signal numbers outside of `1..=126`
+/// can't be represented by a Unix status and are rejected.
+/// Signaled statuses are not supported on other platforms.
+impl StrictType for ExitStatus {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+    fn strict_name() -> Option<TypeName> { Some(tn!("ExitStatus")) }
+}
+impl StrictEncode for ExitStatus {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        ExitStatusRepr::from(self).strict_encode(writer)
+    }
+}
+impl StrictDecode for ExitStatus {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        ExitStatusRepr::strict_decode(reader)?.try_into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::encoding;
+    #[cfg(unix)]
+    use crate::StrictReader;
+
+    #[cfg(unix)]
+    fn decode_err(data: [u8; 5]) {
+        let mut reader = StrictReader::in_memory(data.to_vec(), data.len());
+        assert!(matches!(
+            ExitStatus::strict_decode(&mut reader),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn unix() {
+        use std::os::unix::process::ExitStatusExt;
+
+        encoding(&ExitStatus::from_raw(0), [0, 0, 0, 0, 0]);
+        encoding(&ExitStatus::from_raw(3 << 8), [0, 3, 0, 0, 0]);
+        encoding(&ExitStatus::from_raw(9), [1, 9, 0, 0, 0]);
+        // Stopped by SIGSTOP
+        let stopped = ExitStatus::from_raw(0x137F);
+        assert_eq!(crate::test::encode(&stopped), [2, 0, 0, 0, 0]);
+
+        // Exit code 300, which would be truncated to 44
+        decode_err([0, 0x2C, 1, 0, 0]);
+        // Signal 0, which would be read back as `Exited(0)`
+        decode_err([1, 0, 0, 0, 0]);
+        // Signal 0x7F, which would be read back as `Unknown`
+        decode_err([1, 0x7F, 0, 0, 0]);
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn windows() {
+        use std::os::windows::process::ExitStatusExt;
+
+        encoding(&ExitStatus::from_raw(0), [0, 0, 0, 0, 0]);
+        encoding(&ExitStatus::from_raw(3), [0, 3, 0, 0, 0]);
+    }
+}
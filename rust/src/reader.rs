@@ -22,8 +22,9 @@
 use std::io;
 
 use crate::{
-    DecodeError, FieldName, ReadStruct, ReadTuple, ReadUnion, StrictDecode, StrictEnum,
-    StrictStruct, StrictSum, StrictTuple, StrictUnion, TypedRead, VariantName,
+    CountedItems, DecodeError, FieldName, ReadStruct, ReadTuple, ReadUnion, StrictDecode,
+    StrictEncodedLen, StrictEnum, StrictStruct, StrictSum, StrictTuple, StrictUnion, TypedRead,
+    VariantName,
 };
 
 trait TypedParent: Sized {}
@@ -88,6 +89,99 @@ impl<R: io::Read> io::Read for CountingReader<R> {
     }
 }
 
+/// Reads exactly `N` raw bytes from the reader.
+///
+/// Helper for manual decoding implementations operating on raw data; fails
+/// with [`DecodeError::Io`] if the reader doesn't contain enough data.
+pub fn read_bytes_exact<const N: usize>(
+    reader: &mut impl io::Read,
+) -> Result<[u8; N], DecodeError> {
+    let mut buf = [0u8; N];
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+/// Reads exactly `len` raw bytes from the reader.
+///
+/// Helper for manual decoding implementations operating on raw data; fails
+/// with [`DecodeError::Io`] if the reader doesn't contain enough data.
+pub fn read_bytes_vec(reader: &mut impl io::Read, len: usize) -> Result<Vec<u8>, DecodeError> {
+    let mut buf = vec![0u8; len];
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+/// Advances the reader past a strict-encoded value of type `T`.
+///
+/// The value is decoded and dropped, which validates the skipped data; for
+/// types of a fixed size [`strict_skip_fixed`] avoids decoding.
+pub fn strict_skip<T: StrictDecode, R: io::Read>(reader: &mut R) -> Result<(), DecodeError> {
+    T::strict_decode(&mut StrictReader::with(usize::MAX, reader))?;
+    Ok(())
+}
+
+/// Advances the reader past a strict-encoded value of type `T` by seeking
+/// [`StrictEncodedLen::ENCODED_LEN`] bytes forward, without reading the data.
+///
+/// Since seeking past the end of a stream is allowed, missing data are not
+/// detected by this function and will cause an error only on the next read.
+pub fn strict_skip_fixed<T: StrictEncodedLen, R: io::Read + io::Seek>(
+    reader: &mut R,
+) -> Result<(), DecodeError> {
+    let len =
+        i64::try_from(T::ENCODED_LEN).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+    reader.seek(io::SeekFrom::Current(len))?;
+    Ok(())
+}
+
+macro_rules! decode_le {
+    ($($(#[$attr:meta])* $name:ident: $ty:ty),+ $(,)?) => {
+        $(
+            $(#[$attr])*
+            fn $name(&mut self) -> Result<$ty, DecodeError> {
+                let mut buf = [0u8; core::mem::size_of::<$ty>()];
+                self.read_exact(&mut buf)?;
+                Ok(<$ty>::from_le_bytes(buf))
+            }
+        )+
+    };
+}
+
+/// Methods reading strict-encoded primitive values directly from any
+/// [`io::Read`], for use in manual [`StrictDecode`] implementations.
+pub trait StrictReadExt: io::Read {
+    decode_le! {
+        /// Reads `u8` value.
+        decode_u8: u8,
+        /// Reads `u16` value in little-endian byte order.
+        decode_u16_le: u16,
+        /// Reads `u32` value in little-endian byte order.
+        decode_u32_le: u32,
+        /// Reads `u64` value in little-endian byte order.
+        decode_u64_le: u64,
+        /// Reads `u128` value in little-endian byte order.
+        decode_u128_le: u128,
+        /// Reads `i8` value.
+        decode_i8: i8,
+        /// Reads `i16` value in little-endian byte order.
+        decode_i16_le: i16,
+        /// Reads `i32` value in little-endian byte order.
+        decode_i32_le: i32,
+        /// Reads `i64` value in little-endian byte order.
+        decode_i64_le: i64,
+        /// Reads `i128` value in little-endian byte order.
+        decode_i128_le: i128,
+    }
+
+    /// Reads `bool` value, failing on any byte other than `0` or `1` in the
+    /// same way as [`bool`] strict decoding does.
+    fn decode_bool(&mut self) -> Result<bool, DecodeError> {
+        bool::strict_decode(&mut StrictReader::with(1, self))
+    }
+}
+
+impl<R: io::Read + ?Sized> StrictReadExt for R {}
+
 #[derive(Clone, Debug, From)]
 pub struct StrictReader<R: io::Read>(CountingReader<R>);
 
@@ -223,6 +317,22 @@ impl<'parent, R: io::Read> ReadStruct for StructReader<'parent, R> {
         self.named_fields.push(field);
         T::strict_decode(self.parent)
     }
+
+    fn read_counted<C: CountedItems>(
+        &mut self,
+        field: FieldName,
+        count: usize,
+    ) -> Result<C, DecodeError> {
+        C::check_count(count)?;
+        self.named_fields.push(field);
+        let mut items = Vec::new();
+        for _ in 0..count {
+            items.push(StrictDecode::strict_decode(self.parent)?);
+        }
+        C::from_counted_items(items)
+    }
+
+    fn skip_field(&mut self, field: FieldName) { self.named_fields.push(field); }
 }
 
 impl<R: io::Read> ReadUnion for StrictReader<R> {
@@ -259,3 +369,43 @@ impl<R: io::Read> ReadUnion for StrictReader<R> {
         inner(&mut reader)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::TinyVec;
+
+    use super::*;
+    use crate::test::encode;
+
+    #[test]
+    fn read_ext() {
+        let mut data = encode(&(0xA5u8, 0xCAFEu16, -2i32));
+        data.extend(encode(&1u64));
+        data.extend(encode(&i128::MIN));
+        data.extend([1, 2]);
+
+        let mut cursor = io::Cursor::new(data);
+        assert_eq!(cursor.decode_u8().unwrap(), 0xA5);
+        assert_eq!(cursor.decode_u16_le().unwrap(), 0xCAFE);
+        assert_eq!(cursor.decode_i32_le().unwrap(), -2);
+        assert_eq!(cursor.decode_u64_le().unwrap(), 1);
+        assert_eq!(cursor.decode_i128_le().unwrap(), i128::MIN);
+        assert!(cursor.decode_bool().unwrap());
+        assert!(cursor.decode_bool().is_err());
+        assert!(matches!(cursor.decode_u8(), Err(DecodeError::Io(_))));
+    }
+
+    #[test]
+    fn skip() {
+        let mut data = encode(&TinyVec::try_from(vec![1u16, 2, 3]).unwrap());
+        data.extend(encode(&[0xA5u8; 4]));
+        data.extend(encode(&0xCAFEu16));
+
+        let mut cursor = io::Cursor::new(data);
+        strict_skip::<TinyVec<u16>, _>(&mut cursor).unwrap();
+        assert_eq!(cursor.position(), 7);
+        strict_skip_fixed::<[u8; 4], _>(&mut cursor).unwrap();
+        assert_eq!(cursor.position(), 11);
+        assert_eq!(u16::strict_decode(&mut StrictReader::with(2, &mut cursor)), Ok(0xCAFE));
+    }
+}
@@ -0,0 +1,127 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Truncated hashes of type schemata, which are written before the data of the
+//! structures marked with `#[strict_type(schema_hash_check)]` attribute.
+
+use std::any;
+use std::marker::PhantomData;
+
+use crate::StrictType;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Hasher collecting the schema of a type with [`StrictType::strict_schema`].
+///
+/// Each type hashes its library and type name and, if it is composed of other
+/// types, the names of its fields and variants together with the schemata of
+/// their types. Recursive types hash a back reference to the type which is
+/// already being hashed instead of descending into it again.
+///
+/// The hash is 64-bit FNV-1a, which doesn't depend on the compiler version or
+/// the platform, such that the hash of the same schema is always the same.
+#[derive(Clone, Debug)]
+pub struct SchemaHasher {
+    state: u64,
+    stack: Vec<&'static str>,
+}
+
+impl Default for SchemaHasher {
+    fn default() -> Self { Self::new() }
+}
+
+impl SchemaHasher {
+    pub fn new() -> Self {
+        SchemaHasher {
+            state: FNV_OFFSET_BASIS,
+            stack: vec![],
+        }
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= *byte as u64;
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    /// Hashes an integer value, like a collection length limit.
+    pub fn write_u64(&mut self, val: u64) { self.write_bytes(&val.to_le_bytes()) }
+
+    /// Hashes a string prefixed with its length, such that a sequence of
+    /// strings can't produce the same hash as a different sequence.
+    pub fn write_str(&mut self, s: &str) {
+        self.write_u64(s.len() as u64);
+        self.write_bytes(s.as_bytes());
+    }
+
+    /// Hashes library and type name of the type `T`.
+    pub fn write_name<T: StrictType>(&mut self) {
+        self.write_str(T::STRICT_LIB_NAME);
+        self.write_str(
+            &T::strict_name()
+                .map(|name| name.to_string())
+                .unwrap_or_default(),
+        );
+    }
+
+    /// Hashes schema of the type `T`.
+    pub fn write_type<T: StrictType>(&mut self) {
+        let id = any::type_name::<T>();
+        if let Some(pos) = self.stack.iter().rposition(|ty| *ty == id) {
+            self.write_str("^");
+            self.write_u64((self.stack.len() - pos) as u64);
+            return;
+        }
+        self.stack.push(id);
+        T::strict_schema(self, PhantomData);
+        self.stack.pop();
+    }
+
+    /// Returns the hash truncated to 4 bytes.
+    pub fn finish(&self) -> [u8; 4] { (((self.state >> 32) ^ self.state) as u32).to_le_bytes() }
+}
+
+/// Computes 4-byte hash of the schema of the type `T`.
+pub fn schema_hash<T: StrictType>() -> [u8; 4] {
+    let mut hasher = SchemaHasher::new();
+    hasher.write_type::<T>();
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::{SmallVec, TinyVec};
+
+    use super::*;
+
+    #[test]
+    fn nested_types() {
+        assert_eq!(schema_hash::<u8>(), schema_hash::<u8>());
+        assert_ne!(schema_hash::<u8>(), schema_hash::<u16>());
+        assert_ne!(schema_hash::<TinyVec<u8>>(), schema_hash::<TinyVec<u16>>());
+        assert_ne!(schema_hash::<TinyVec<u8>>(), schema_hash::<SmallVec<u8>>());
+        assert_ne!(schema_hash::<Option<u8>>(), schema_hash::<u8>());
+        assert_ne!(schema_hash::<(u8, u16)>(), schema_hash::<(u16, u8)>());
+        assert_eq!(schema_hash::<Box<u8>>(), schema_hash::<u8>());
+    }
+}
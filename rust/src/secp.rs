@@ -0,0 +1,165 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strict encoding for keys and signatures from `secp256k1` crate.
+
+use std::io;
+
+use amplify::confinement::Confined;
+use secp256k1::{ecdsa, schnorr, PublicKey, SecretKey};
+
+use crate::{
+    DecodeError, StrictDecode, StrictEncode, StrictEncodeSize, StrictEncodedLen, StrictType,
+    TypeName, TypedRead, TypedWrite, LIB_NAME_STD,
+};
+
+/// Maximal length of a DER-encoded ECDSA signature.
+pub const DER_SIGNATURE_MAX_LEN: usize = 72;
+
+// Public keys are always encoded in the compressed form.
+impl_fixed!(
+    PublicKey,
+    33,
+    "Secp256k1PublicKey",
+    "secp256k1 public key",
+    |key| key.serialize(),
+    |bytes| PublicKey::from_byte_array_compressed(&bytes)
+);
+impl_fixed!(
+    SecretKey,
+    32,
+    "Secp256k1SecretKey",
+    "secp256k1 secret key",
+    |key| key.secret_bytes(),
+    |bytes| SecretKey::from_byte_array(&bytes)
+);
+impl_fixed!(
+    ecdsa::Signature,
+    64,
+    "EcdsaSignature",
+    "compact ECDSA signature",
+    |sig| sig.serialize_compact(),
+    |bytes| ecdsa::Signature::from_compact(&bytes)
+);
+impl_fixed!(
+    schnorr::Signature,
+    64,
+    "SchnorrSignature",
+    "Schnorr signature",
+    |sig| sig.to_byte_array(),
+    |bytes| schnorr::Signature::from_slice(&bytes)
+);
+
+/// ECDSA signature which is strict-encoded in DER format, prefixed with its
+/// length, instead of the compact format used for [`ecdsa::Signature`].
+#[derive(Wrapper, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, From)]
+#[wrapper(Deref)]
+pub struct DerSignature(ecdsa::Signature);
+
+impl StrictType for DerSignature {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+    fn strict_name() -> Option<TypeName> { Some(tn!("DerSignature")) }
+}
+impl StrictEncode for DerSignature {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        Confined::<Vec<u8>, 0, DER_SIGNATURE_MAX_LEN>::try_from(self.0.serialize_der().to_vec())
+            .expect("DER-encoded signature never exceeds 72 bytes")
+            .strict_encode(writer)
+    }
+}
+impl StrictDecode for DerSignature {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let der = Confined::<Vec<u8>, 0, DER_SIGNATURE_MAX_LEN>::strict_decode(reader)?;
+        ecdsa::Signature::from_der(&der)
+            .map(DerSignature)
+            .map_err(|err| {
+                DecodeError::DataIntegrityError(format!("invalid DER ECDSA signature: {err}"))
+            })
+    }
+}
+impl StrictEncodeSize for DerSignature {
+    fn encoded_size(&self) -> usize { 1 + self.0.serialize_der().len() }
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::hex::FromHex;
+    use secp256k1::{Keypair, Message, Secp256k1};
+
+    use super::*;
+    use crate::test::{encode, encoding};
+    use crate::StrictReader;
+
+    // Generator point G, which is the public key for the secret key 1
+    const GENERATOR: &str = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+    fn decode_err<T: StrictDecode + std::fmt::Debug>(data: Vec<u8>) {
+        let len = data.len();
+        let mut reader = StrictReader::in_memory(data, len);
+        assert!(matches!(T::strict_decode(&mut reader), Err(DecodeError::DataIntegrityError(_))));
+    }
+
+    #[test]
+    fn keys() {
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        let secret = SecretKey::from_byte_array(&one).unwrap();
+        let public = PublicKey::from_secret_key(&Secp256k1::new(), &secret);
+
+        encoding(&secret, one);
+        encoding(&public, <[u8; 33]>::from_hex(GENERATOR).unwrap());
+        assert_eq!(public.encoded_size(), encode(&public).len());
+
+        decode_err::<SecretKey>(vec![0u8; 32]);
+        decode_err::<PublicKey>(vec![0x05; 33]);
+
+        assert_eq!(PublicKey::strict_name(), Some(tn!("Secp256k1PublicKey")));
+        assert_eq!(SecretKey::strict_name(), Some(tn!("Secp256k1SecretKey")));
+    }
+
+    #[test]
+    fn signatures() {
+        let secp = Secp256k1::new();
+        let secret = SecretKey::from_byte_array(&[0xCD; 32]).unwrap();
+        let msg = Message::from_digest([0xAB; 32]);
+
+        let sig = secp.sign_ecdsa(&msg, &secret);
+        encoding(&sig, sig.serialize_compact());
+        assert_eq!(sig.encoded_size(), encode(&sig).len());
+
+        let der = DerSignature::from(sig);
+        let mut expected = vec![sig.serialize_der().len() as u8];
+        expected.extend_from_slice(&sig.serialize_der());
+        encoding(&der, expected);
+        assert_eq!(der.encoded_size(), encode(&der).len());
+
+        let keypair = Keypair::from_secret_key(&secp, &secret);
+        let schnorr = secp.sign_schnorr_no_aux_rand(&[0xAB; 32], &keypair);
+        encoding(&schnorr, schnorr.to_byte_array());
+
+        decode_err::<ecdsa::Signature>(vec![0xFF; 64]);
+        decode_err::<DerSignature>(vec![4, 0x30, 0x02, 0x02, 0x01]);
+
+        assert_eq!(ecdsa::Signature::strict_name(), Some(tn!("EcdsaSignature")));
+        assert_eq!(schnorr::Signature::strict_name(), Some(tn!("SchnorrSignature")));
+        assert_eq!(DerSignature::strict_name(), Some(tn!("DerSignature")));
+    }
+}
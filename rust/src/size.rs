@@ -0,0 +1,262 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Computing size of the strict encoding without performing the encoding.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::hash::Hash;
+
+use amplify::ascii::AsciiString;
+use amplify::confinement::Confined;
+#[cfg(feature = "float")]
+use amplify::num::apfloat::ieee;
+use amplify::num::{i1024, i256, i512, u1024, u24, u256, u4, u512};
+use amplify::{Array, Wrapper};
+
+use crate::{Byte, StrictDumb, StrictEncode, StrictEncodeInplace};
+
+/// Types which can report the size of their strict encoding, in bytes,
+/// without actually encoding the data.
+///
+/// The reported size must always match the number of bytes produced by
+/// [`StrictEncode::strict_encode`].
+pub trait StrictEncodeSize: StrictEncode {
+    fn encoded_size(&self) -> usize;
+}
+
+/// Types which strict encoding always takes the same number of bytes,
+/// independently of the value.
+pub trait StrictEncodedLen: StrictEncodeSize {
+    const ENCODED_LEN: usize;
+}
+
+/// Returns size of the length prefix used by collections confined to at most
+/// `MAX_LEN` items.
+pub const fn len_prefix_size<const MAX_LEN: usize>() -> usize {
+    match MAX_LEN {
+        tiny if tiny <= u8::MAX as usize => 1,
+        small if small <= u16::MAX as usize => 2,
+        medium if medium <= u24::MAX.into_usize() => 3,
+        large if large <= u32::MAX as usize => 4,
+        _ => 8,
+    }
+}
+
+macro_rules! size_fixed {
+    ($($ty:ty => $len:expr),+ $(,)?) => {
+        $(
+            impl StrictEncodeSize for $ty {
+                #[inline]
+                fn encoded_size(&self) -> usize { $len }
+            }
+            impl StrictEncodedLen for $ty {
+                const ENCODED_LEN: usize = $len;
+            }
+            impl StrictEncodeInplace for $ty {}
+        )+
+    };
+}
+
+size_fixed! {
+    () => 0,
+    bool => 1,
+    u4 => 1,
+    Byte => 1,
+    u8 => 1,
+    u16 => 2,
+    u24 => 3,
+    u32 => 4,
+    u64 => 8,
+    u128 => 16,
+    u256 => 32,
+    u512 => 64,
+    u1024 => 128,
+    i8 => 1,
+    i16 => 2,
+    i32 => 4,
+    i64 => 8,
+    i128 => 16,
+    i256 => 32,
+    i512 => 64,
+    i1024 => 128,
+}
+
+#[cfg(feature = "float")]
+size_fixed! {
+    ieee::Half => 2,
+    ieee::Single => 4,
+    ieee::Double => 8,
+    ieee::X87DoubleExtended => 10,
+    ieee::Quad => 16,
+    ieee::Oct => 32,
+}
+
+#[cfg(feature = "half")]
+size_fixed! {
+    half::f16 => 2,
+    half::bf16 => 2,
+}
+
+impl<T: StrictEncodeSize> StrictEncodeSize for Box<T> {
+    fn encoded_size(&self) -> usize { self.as_ref().encoded_size() }
+}
+
+impl<T: StrictEncodeSize + StrictDumb> StrictEncodeSize for Option<T> {
+    fn encoded_size(&self) -> usize { 1 + self.as_ref().map_or(0, T::encoded_size) }
+}
+
+impl<A: StrictEncodeSize + Default, B: StrictEncodeSize + Default> StrictEncodeSize for (A, B) {
+    fn encoded_size(&self) -> usize { self.0.encoded_size() + self.1.encoded_size() }
+}
+
+impl<A, B, C> StrictEncodeSize for (A, B, C)
+where
+    A: StrictEncodeSize + Default,
+    B: StrictEncodeSize + Default,
+    C: StrictEncodeSize + Default,
+{
+    fn encoded_size(&self) -> usize {
+        self.0.encoded_size() + self.1.encoded_size() + self.2.encoded_size()
+    }
+}
+
+impl<T: StrictEncodeSize + Copy + StrictDumb, const LEN: usize> StrictEncodeSize for [T; LEN] {
+    fn encoded_size(&self) -> usize { self.iter().map(T::encoded_size).sum() }
+}
+
+impl<T: StrictEncodeSize + Copy + StrictDumb, const LEN: usize> StrictEncodeSize for Array<T, LEN> {
+    fn encoded_size(&self) -> usize { self.as_inner().encoded_size() }
+}
+
+impl<T: StrictEncodedLen + Copy + StrictDumb, const LEN: usize> StrictEncodedLen for [T; LEN] {
+    const ENCODED_LEN: usize = T::ENCODED_LEN * LEN;
+}
+
+impl<T: StrictEncodedLen + Copy + StrictDumb, const LEN: usize> StrictEncodedLen for Array<T, LEN> {
+    const ENCODED_LEN: usize = T::ENCODED_LEN * LEN;
+}
+
+impl<const MIN_LEN: usize, const MAX_LEN: usize> StrictEncodeSize
+    for Confined<String, MIN_LEN, MAX_LEN>
+{
+    fn encoded_size(&self) -> usize { len_prefix_size::<MAX_LEN>() + self.len() }
+}
+
+impl<const MIN_LEN: usize, const MAX_LEN: usize> StrictEncodeSize
+    for Confined<AsciiString, MIN_LEN, MAX_LEN>
+{
+    fn encoded_size(&self) -> usize { len_prefix_size::<MAX_LEN>() + self.len() }
+}
+
+impl<T: StrictEncodeSize + StrictDumb, const MIN_LEN: usize, const MAX_LEN: usize> StrictEncodeSize
+    for Confined<Vec<T>, MIN_LEN, MAX_LEN>
+{
+    fn encoded_size(&self) -> usize {
+        len_prefix_size::<MAX_LEN>() + self.iter().map(T::encoded_size).sum::<usize>()
+    }
+}
+
+impl<T: StrictEncodeSize + StrictDumb, const MIN_LEN: usize, const MAX_LEN: usize> StrictEncodeSize
+    for Confined<VecDeque<T>, MIN_LEN, MAX_LEN>
+{
+    fn encoded_size(&self) -> usize {
+        len_prefix_size::<MAX_LEN>() + self.iter().map(T::encoded_size).sum::<usize>()
+    }
+}
+
+impl<T: StrictEncodeSize + Ord + StrictDumb, const MIN_LEN: usize, const MAX_LEN: usize>
+    StrictEncodeSize for Confined<BTreeSet<T>, MIN_LEN, MAX_LEN>
+{
+    fn encoded_size(&self) -> usize {
+        len_prefix_size::<MAX_LEN>() + self.iter().map(T::encoded_size).sum::<usize>()
+    }
+}
+
+impl<K, V, const MIN_LEN: usize, const MAX_LEN: usize> StrictEncodeSize
+    for Confined<BTreeMap<K, V>, MIN_LEN, MAX_LEN>
+where
+    K: StrictEncodeSize + Ord + Hash + StrictDumb,
+    V: StrictEncodeSize + StrictDumb,
+{
+    fn encoded_size(&self) -> usize {
+        len_prefix_size::<MAX_LEN>() +
+            self.iter()
+                .map(|(k, v)| k.encoded_size() + v.encoded_size())
+                .sum::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fmt::Debug;
+
+    use amplify::confinement::{
+        LargeVec, MediumString, SmallOrdMap, TinyAscii, TinyDeque, TinyOrdSet, TinyString,
+    };
+
+    use super::*;
+    use crate::test::encode;
+
+    fn check<T: StrictEncodeSize + Debug + PartialEq>(val: T) {
+        assert_eq!(val.encoded_size(), encode(&val).len(), "wrong size for {val:?}");
+    }
+
+    #[test]
+    fn primitives() {
+        check(());
+        check(true);
+        check(0xA5u8);
+        check(u24::with(0xABCDEF));
+        check(-1i64);
+        check(u256::ONE);
+        check(i1024::ONE);
+    }
+
+    #[test]
+    fn composite() {
+        check(Some(0xCAFEu16));
+        check(None::<u16>);
+        check(Box::new(7u32));
+        check((1u8, 2u64));
+        check((1u8, Some(2u16), 3u32));
+        check([Some(1u8), None, Some(3)]);
+    }
+
+    #[test]
+    fn collections() {
+        check(MediumString::try_from(s!("strict")).unwrap());
+        check(TinyAscii::try_from(AsciiString::from_ascii("ascii").unwrap()).unwrap());
+        check(LargeVec::try_from(vec![1u16, 2, 3]).unwrap());
+        check(TinyDeque::try_from(VecDeque::from(vec![1u32, 2])).unwrap());
+        check(TinyOrdSet::try_from(bset! { 1u8, 2, 3 }).unwrap());
+        let tiny = |s: &str| TinyString::try_from(s.to_owned()).unwrap();
+        check(SmallOrdMap::try_from(bmap! { 1u8 => tiny("a"), 2u8 => tiny("bc") }).unwrap());
+    }
+
+    #[test]
+    fn fixed_len() {
+        assert_eq!(<()>::ENCODED_LEN, 0);
+        assert_eq!(u24::ENCODED_LEN, 3);
+        assert_eq!(<[u16; 4]>::ENCODED_LEN, 8);
+        #[cfg(feature = "half")]
+        assert_eq!(half::f16::ENCODED_LEN, 2);
+    }
+}
@@ -0,0 +1,182 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strict encoding for socket addresses from `socket2` crate.
+
+use std::io;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
+
+use amplify::confinement::Confined;
+use socket2::SockAddr;
+
+use crate::{
+    DecodeError, StrictDecode, StrictDumb, StrictEncode, StrictType, TypeName, TypedRead,
+    TypedWrite, LIB_NAME_STD,
+};
+
+/// Maximal length of a Unix socket path (size of `sun_path` in `sockaddr_un`
+/// on Linux).
+const UNIX_PATH_MAX_LEN: usize = 108;
+
+/// Strict type representation of [`SockAddr`], which is encoded as an address
+/// family tag followed by the address data.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STD, tags = order, rename = "SockAddr", crate = crate)]
+enum SockAddrRepr {
+    Ipv4 {
+        ip: [u8; 4],
+        port: u16,
+    },
+    Ipv6 {
+        ip: [u8; 16],
+        port: u16,
+        flowinfo: u32,
+        scope_id: u32,
+    },
+    Unix(Confined<Vec<u8>, 0, UNIX_PATH_MAX_LEN>),
+}
+
+impl StrictDumb for SockAddrRepr {
+    fn strict_dumb() -> Self {
+        SockAddrRepr::Ipv4 {
+            ip: [0; 4],
+            port: 0,
+        }
+    }
+}
+
+impl TryFrom<&SockAddr> for SockAddrRepr {
+    type Error = io::Error;
+
+    fn try_from(addr: &SockAddr) -> Result<Self, Self::Error> {
+        if let Some(addr) = addr.as_socket_ipv4() {
+            return Ok(SockAddrRepr::Ipv4 {
+                ip: addr.ip().octets(),
+                port: addr.port(),
+            });
+        }
+        if let Some(addr) = addr.as_socket_ipv6() {
+            return Ok(SockAddrRepr::Ipv6 {
+                ip: addr.ip().octets(),
+                port: addr.port(),
+                flowinfo: addr.flowinfo(),
+                scope_id: addr.scope_id(),
+            });
+        }
+        #[cfg(unix)]
+        if let Some(path) = addr.as_pathname() {
+            use std::os::unix::ffi::OsStrExt;
+            return Confined::try_from(path.as_os_str().as_bytes().to_vec())
+                .map(SockAddrRepr::Unix)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err));
+        }
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "only IPv4, IPv6 and pathname Unix socket addresses can be strict-encoded",
+        ))
+    }
+}
+
+impl TryFrom<SockAddrRepr> for SockAddr {
+    type Error = DecodeError;
+
+    fn try_from(repr: SockAddrRepr) -> Result<Self, Self::Error> {
+        Ok(match repr {
+            SockAddrRepr::Ipv4 { ip, port } => SocketAddrV4::new(Ipv4Addr::from(ip), port).into(),
+            SockAddrRepr::Ipv6 {
+                ip,
+                port,
+                flowinfo,
+                scope_id,
+            } => SocketAddrV6::new(Ipv6Addr::from(ip), port, flowinfo, scope_id).into(),
+            SockAddrRepr::Unix(path) if path.is_empty() => {
+                return Err(DecodeError::DataIntegrityError(s!("empty Unix socket path")));
+            }
+            #[cfg(unix)]
+            SockAddrRepr::Unix(path) => {
+                use std::ffi::OsStr;
+                use std::os::unix::ffi::OsStrExt;
+                SockAddr::unix(OsStr::from_bytes(path.as_slice())).map_err(|err| {
+                    DecodeError::DataIntegrityError(format!("invalid Unix socket path: {err}"))
+                })?
+            }
+            #[cfg(not(unix))]
+            SockAddrRepr::Unix(_) => {
+                return Err(DecodeError::DataIntegrityError(s!(
+                    "Unix socket addresses are not supported on this platform"
+                )));
+            }
+        })
+    }
+}
+
+impl StrictType for SockAddr {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+    fn strict_name() -> Option<TypeName> { SockAddrRepr::strict_name() }
+}
+impl StrictEncode for SockAddr {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        SockAddrRepr::try_from(self)?.strict_encode(writer)
+    }
+}
+impl StrictDecode for SockAddr {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        SockAddrRepr::strict_decode(reader)?.try_into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::SocketAddr;
+
+    use super::*;
+    use crate::test::encoding;
+    use crate::StrictReader;
+
+    #[test]
+    fn ipv4() {
+        let addr = SockAddr::from(SocketAddr::from(([127, 0, 0, 1], 8080)));
+        encoding(&addr, [0x00, 127, 0, 0, 1, 0x90, 0x1F]);
+    }
+
+    #[test]
+    fn ipv6() {
+        let addr = SockAddr::from(SocketAddrV6::new(Ipv6Addr::LOCALHOST, 8080, 7, 3));
+        let mut data = vec![0x01];
+        data.extend(Ipv6Addr::LOCALHOST.octets());
+        data.extend([0x90, 0x1F, 7, 0, 0, 0, 3, 0, 0, 0]);
+        encoding(&addr, data);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn unix() {
+        let addr = SockAddr::unix("/tmp/strict.sock").unwrap();
+        encoding(&addr, b"\x02\x10/tmp/strict.sock");
+
+        let mut reader = StrictReader::in_memory(vec![0x02, 0x00], 2);
+        assert!(matches!(
+            SockAddr::strict_decode(&mut reader),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+    }
+}
@@ -0,0 +1,173 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compact encoding for string-to-string maps.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+
+use amplify::confinement::Confined;
+
+use crate::{
+    DecodeError, StrictDecode, StrictEncode, StrictType, TypeName, TypedRead, TypedWrite,
+    LIB_NAME_STD,
+};
+
+/// Maximal number of entries in a string map.
+const MAX_ENTRIES: usize = u16::MAX as usize;
+
+type MapKey = Confined<String, 0, 0xFF>;
+type MapValue = Confined<String, 0, { u16::MAX as usize }>;
+type MapEntries = Confined<BTreeMap<MapKey, MapValue>, 0, MAX_ENTRIES>;
+
+fn encode_entries<'a, W: TypedWrite>(
+    entries: impl Iterator<Item = (&'a String, &'a String)>,
+    writer: W,
+) -> io::Result<W> {
+    let invalid = |err| io::Error::new(io::ErrorKind::InvalidInput, err);
+    let mut map = BTreeMap::new();
+    for (key, value) in entries {
+        if key.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "empty string map key"));
+        }
+        let key = MapKey::try_from(key.clone()).map_err(invalid)?;
+        let value = MapValue::try_from(value.clone()).map_err(invalid)?;
+        map.insert(key, value);
+    }
+    MapEntries::try_from(map)
+        .map_err(invalid)?
+        .strict_encode(writer)
+}
+
+/// Reads map entries, checking that the keys are non-empty, unique and
+/// sorted.
+fn decode_entries(
+    reader: &mut impl TypedRead,
+    mut insert: impl FnMut(String, String),
+) -> Result<(), DecodeError> {
+    let len = unsafe { reader._read_raw_len::<MAX_ENTRIES>()? };
+    let mut last = None::<MapKey>;
+    for _ in 0..len {
+        let key = MapKey::strict_decode(reader)?;
+        let value = MapValue::strict_decode(reader)?;
+        if key.is_empty() {
+            return Err(DecodeError::DataIntegrityError(s!("empty string map key")));
+        }
+        match &last {
+            Some(last) if last == &key => {
+                return Err(DecodeError::DuplicateAttributeKey(key.into_inner()));
+            }
+            Some(last) if last > &key => return Err(DecodeError::BrokenMapOrder),
+            _ => {}
+        }
+        insert(key.to_string(), value.into_inner());
+        last = Some(key);
+    }
+    Ok(())
+}
+
+/// String map is encoded as a map of up to 2^16-1 entries ordered by their
+/// keys. Each key is a non-empty UTF-8 string of at most 255 bytes with `u8`
+/// length prefix, and each value is a UTF-8 string with `u16` length prefix.
+impl StrictType for HashMap<String, String> {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+    fn strict_name() -> Option<TypeName> { Some(tn!("StringMap")) }
+}
+impl StrictEncode for HashMap<String, String> {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        encode_entries(self.iter(), writer)
+    }
+}
+impl StrictDecode for HashMap<String, String> {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let mut map = HashMap::new();
+        decode_entries(reader, |key, value| {
+            map.insert(key, value);
+        })?;
+        Ok(map)
+    }
+}
+
+/// Encoded in the same way as [`HashMap<String, String>`].
+impl StrictType for BTreeMap<String, String> {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+    fn strict_name() -> Option<TypeName> { Some(tn!("StringMap")) }
+}
+impl StrictEncode for BTreeMap<String, String> {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        encode_entries(self.iter(), writer)
+    }
+}
+impl StrictDecode for BTreeMap<String, String> {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let mut map = BTreeMap::new();
+        decode_entries(reader, |key, value| {
+            map.insert(key, value);
+        })?;
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::encoding;
+    use crate::{StrictDumb, StrictReader};
+
+    #[test]
+    fn string_map() {
+        let data = b"\x02\x00\x01a\x01\x00x\x02bc\x00\x00";
+        let mut map = HashMap::new();
+        map.insert(s!("bc"), s!(""));
+        map.insert(s!("a"), s!("x"));
+        encoding(&map, data);
+        encoding(&map.into_iter().collect::<BTreeMap<_, _>>(), data);
+
+        assert!(HashMap::<String, String>::strict_dumb().is_empty());
+        assert!(BTreeMap::<String, String>::strict_dumb().is_empty());
+    }
+
+    #[test]
+    fn string_map_invalid() {
+        let decode = |data: &[u8]| {
+            let mut reader = StrictReader::in_memory(data.to_vec(), 0xFF);
+            BTreeMap::<String, String>::strict_decode(&mut reader)
+        };
+        assert_eq!(
+            decode(b"\x02\x00\x01a\x00\x00\x01a\x00\x00"),
+            Err(DecodeError::DuplicateAttributeKey(s!("a")))
+        );
+        assert_eq!(decode(b"\x02\x00\x01b\x00\x00\x01a\x00\x00"), Err(DecodeError::BrokenMapOrder));
+        assert!(matches!(decode(b"\x01\x00\x00\x00\x00"), Err(DecodeError::DataIntegrityError(_))));
+
+        let mut map = HashMap::new();
+        map.insert(String::new(), s!("x"));
+        assert!(map
+            .strict_encode(crate::StrictWriter::in_memory(0xFF))
+            .is_err());
+
+        let mut map = HashMap::new();
+        map.insert("k".repeat(0x100), s!("x"));
+        assert!(map
+            .strict_encode(crate::StrictWriter::in_memory(0x1000))
+            .is_err());
+    }
+}
@@ -27,7 +27,7 @@ use amplify::confinement::Confined;
 
 use crate::{StrictDecode, StrictEncode, StrictReader, StrictWriter};
 
-pub fn encode<T: StrictEncode + Debug + Eq>(val: &T) -> Vec<u8> {
+pub fn encode<T: StrictEncode + Debug + PartialEq>(val: &T) -> Vec<u8> {
     const MAX: usize = u16::MAX as usize;
 
     let ast_data = StrictWriter::in_memory(MAX);
@@ -37,27 +37,30 @@ pub fn encode<T: StrictEncode + Debug + Eq>(val: &T) -> Vec<u8> {
         .into_inner()
 }
 
-pub fn decode<T: StrictDecode + Debug + Eq>(data: impl AsRef<[u8]>) -> T {
+pub fn decode<T: StrictDecode + Debug + PartialEq>(data: impl AsRef<[u8]>) -> T {
     const MAX: usize = u16::MAX as usize;
 
     let cursor = io::Cursor::new(data);
     let mut reader = StrictReader::with(MAX, cursor);
     let val2 = T::strict_decode(&mut reader).unwrap();
     let mut cursor = reader.unbox();
-    assert!(!cursor.fill_buf().unwrap().is_empty(), "data not entirely consumed");
+    assert!(cursor.fill_buf().unwrap().is_empty(), "data not entirely consumed");
 
     val2
 }
 
 #[allow(dead_code)]
-pub fn encoding_roundtrip<T: StrictEncode + StrictDecode + Debug + Eq>(val: &T) {
+pub fn encoding_roundtrip<T: StrictEncode + StrictDecode + Debug + PartialEq>(val: &T) {
     let data = encode(val);
     let val2: T = decode(data);
     assert_eq!(val, &val2);
 }
 
 #[allow(dead_code)]
-pub fn encoding<T: StrictEncode + StrictDecode + Debug + Eq>(val: &T, expect: impl AsRef<[u8]>) {
+pub fn encoding<T: StrictEncode + StrictDecode + Debug + PartialEq>(
+    val: &T,
+    expect: impl AsRef<[u8]>,
+) {
     let data = encode(val);
     assert_eq!(&data[..], expect.as_ref());
     let val2: T = decode(data);
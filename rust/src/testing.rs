@@ -0,0 +1,168 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Property-based round-trip testing helpers built on top of `proptest`.
+
+use std::fmt::Debug;
+
+use amplify::confinement::{TinyString, TinyVec};
+pub use proptest;
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+
+use crate::{StrictDecode, StrictEncode, StrictReader, StrictType, StrictWriter, LIB_EMBEDDED};
+
+/// Maximal length of the data produced by [`check_roundtrip`].
+pub const ROUNDTRIP_MAX_LEN: usize = u16::MAX as usize;
+
+/// Checks that the value encodes and decodes back into the same value,
+/// consuming all the encoded data.
+pub fn check_roundtrip<T>(val: &T) -> Result<(), TestCaseError>
+where T: StrictEncode + StrictDecode + Debug + PartialEq {
+    let writer = StrictWriter::in_memory(ROUNDTRIP_MAX_LEN);
+    let data = val
+        .strict_encode(writer)
+        .map_err(|err| TestCaseError::fail(err.to_string()))?
+        .unbox();
+    let len = data.len();
+    let mut reader = StrictReader::in_memory(data, ROUNDTRIP_MAX_LEN);
+    let val2 = T::strict_decode(&mut reader).map_err(|err| TestCaseError::fail(err.to_string()))?;
+    prop_assert_eq!(reader.unbox().position() as usize, len, "data not entirely consumed");
+    prop_assert_eq!(val, &val2);
+    Ok(())
+}
+
+/// Strategy producing byte vectors of up to 255 bytes.
+pub fn tiny_blob() -> impl Strategy<Value = TinyVec<u8>> {
+    prop::collection::vec(any::<u8>(), 0..=0xFF)
+        .prop_map(|vec| TinyVec::try_from(vec).expect("vector length is within bounds"))
+}
+
+/// Strategy producing UTF-8 strings which fit into 255 bytes.
+pub fn tiny_string() -> impl Strategy<Value = TinyString> {
+    prop::collection::vec(any::<char>(), 0..=0xFF / 4).prop_map(|chars| {
+        TinyString::try_from(String::from_iter(chars)).expect("string length is within bounds")
+    })
+}
+
+/// Structure containing a value of each primitive type supported by the
+/// strict encoding.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_EMBEDDED, crate = crate)]
+pub struct AllPrimitiveTypes {
+    pub uint8: u8,
+    pub uint16: u16,
+    pub uint32: u32,
+    pub uint64: u64,
+    pub uint128: u128,
+    pub int8: i8,
+    pub int16: i16,
+    pub int32: i32,
+    pub int64: i64,
+    pub int128: i128,
+    pub boolean: bool,
+    pub option: Option<u32>,
+    pub blob: TinyVec<u8>,
+    pub string: TinyString,
+}
+
+impl Arbitrary for AllPrimitiveTypes {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            (any::<u8>(), any::<u16>(), any::<u32>(), any::<u64>(), any::<u128>()),
+            (any::<i8>(), any::<i16>(), any::<i32>(), any::<i64>(), any::<i128>()),
+            (any::<bool>(), any::<Option<u32>>(), tiny_blob(), tiny_string()),
+        )
+            .prop_map(
+                |(
+                    (uint8, uint16, uint32, uint64, uint128),
+                    (int8, int16, int32, int64, int128),
+                    (boolean, option, blob, string),
+                )| AllPrimitiveTypes {
+                    uint8,
+                    uint16,
+                    uint32,
+                    uint64,
+                    uint128,
+                    int8,
+                    int16,
+                    int32,
+                    int64,
+                    int128,
+                    boolean,
+                    option,
+                    blob,
+                    string,
+                },
+            )
+            .boxed()
+    }
+}
+
+/// Generates proptest round-trip test cases, one per each `name in strategy`
+/// pair.
+///
+/// ```ignore
+/// roundtrip_strategy! {
+///     roundtrip_u32 in any::<u32>(),
+///     roundtrip_blob in tiny_blob(),
+/// }
+/// ```
+#[macro_export]
+macro_rules! roundtrip_strategy {
+    ($($name:ident in $strategy:expr),+ $(,)?) => {
+        $crate::testing::proptest::proptest! {
+            $(
+                #[test]
+                fn $name(val in $strategy) {
+                    $crate::testing::check_roundtrip(&val)?;
+                }
+            )+
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    roundtrip_strategy! {
+        roundtrip_u8 in any::<u8>(),
+        roundtrip_u16 in any::<u16>(),
+        roundtrip_u32 in any::<u32>(),
+        roundtrip_u64 in any::<u64>(),
+        roundtrip_u128 in any::<u128>(),
+        roundtrip_i8 in any::<i8>(),
+        roundtrip_i16 in any::<i16>(),
+        roundtrip_i32 in any::<i32>(),
+        roundtrip_i64 in any::<i64>(),
+        roundtrip_i128 in any::<i128>(),
+        roundtrip_bool in any::<bool>(),
+        roundtrip_option in any::<Option<u32>>(),
+        roundtrip_blob in tiny_blob(),
+        roundtrip_string in tiny_string(),
+        roundtrip_all in any::<AllPrimitiveTypes>(),
+    }
+}
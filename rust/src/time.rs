@@ -0,0 +1,97 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strict encoding for [`Instant`], which has no absolute epoch and thus is
+//! encoded as a `u64` number of milliseconds passed since [`ProcessEpoch`].
+//!
+//! Decoded values are meaningful only within the same thread of the same
+//! process which has encoded them, and only if the epoch was not reset in
+//! between. Instants preceding the epoch are encoded as the epoch itself.
+
+use std::cell::Cell;
+use std::io;
+use std::time::{Duration, Instant};
+
+use crate::{
+    DecodeError, StrictDecode, StrictEncode, StrictType, TypedRead, TypedWrite, LIB_EMBEDDED,
+};
+
+thread_local! {
+    static EPOCH: Cell<Instant> = Cell::new(Instant::now());
+}
+
+/// Thread-local epoch used for encoding [`Instant`] values.
+pub struct ProcessEpoch;
+
+impl ProcessEpoch {
+    /// Returns current epoch, initializing it on a first access from the
+    /// thread.
+    pub fn get() -> Instant { EPOCH.with(Cell::get) }
+
+    /// Moves the epoch to the current moment (useful for test isolation).
+    pub fn reset() { EPOCH.with(|epoch| epoch.set(Instant::now())) }
+}
+
+impl StrictType for Instant {
+    const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+}
+impl StrictEncode for Instant {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        let millis = self
+            .saturating_duration_since(ProcessEpoch::get())
+            .as_millis();
+        u64::try_from(millis)
+            .unwrap_or(u64::MAX)
+            .strict_encode(writer)
+    }
+}
+impl StrictDecode for Instant {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let millis = u64::strict_decode(reader)?;
+        ProcessEpoch::get()
+            .checked_add(Duration::from_millis(millis))
+            .ok_or_else(|| {
+                DecodeError::DataIntegrityError(format!(
+                    "instant {millis} ms after the epoch can't be represented"
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::{decode, encode};
+
+    #[test]
+    fn instant() {
+        ProcessEpoch::reset();
+        let epoch = ProcessEpoch::get();
+        assert_eq!(encode(&epoch), [0u8; 8]);
+
+        let instant = epoch + Duration::from_millis(0x1234);
+        assert_eq!(encode(&instant), [0x34, 0x12, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(decode::<Instant>(encode(&instant)), instant);
+
+        ProcessEpoch::reset();
+        assert_eq!(encode(&epoch), [0u8; 8]);
+    }
+}
@@ -0,0 +1,89 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strict encoding for ASCII strings from `tinystr` crate.
+
+use std::io;
+
+use amplify::ascii::AsciiStr;
+use tinystr::TinyAsciiStr;
+
+use crate::{
+    DecodeError, StrictDecode, StrictEncode, StrictEncodeSize, StrictEncodedLen, StrictType,
+    TypedRead, TypedWrite,
+};
+
+/// [`TinyAsciiStr<N>`] is encoded as its raw `N` bytes, where the string is
+/// padded with zero bytes if it is shorter than `N` characters.
+impl<const N: usize> StrictType for TinyAsciiStr<N> {
+    const STRICT_LIB_NAME: &'static str = <[u8; N]>::STRICT_LIB_NAME;
+}
+impl<const N: usize> StrictEncode for TinyAsciiStr<N> {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        self.all_bytes().strict_encode(writer)
+    }
+}
+impl<const N: usize> StrictDecode for TinyAsciiStr<N> {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let raw = <[u8; N]>::strict_decode(reader)?;
+        AsciiStr::from_ascii(&raw)?;
+        TinyAsciiStr::try_from_raw(raw).map_err(|err| {
+            DecodeError::DataIntegrityError(format!("invalid tiny ASCII string: {err}"))
+        })
+    }
+}
+impl<const N: usize> StrictEncodeSize for TinyAsciiStr<N> {
+    fn encoded_size(&self) -> usize { Self::ENCODED_LEN }
+}
+impl<const N: usize> StrictEncodedLen for TinyAsciiStr<N> {
+    const ENCODED_LEN: usize = N;
+}
+
+#[cfg(test)]
+mod test {
+    use tinystr::{tinystr, TinyStr4, TinyStr8};
+
+    use super::*;
+    use crate::test::encoding;
+    use crate::StrictReader;
+
+    #[test]
+    fn tiny_str() {
+        encoding(&tinystr!(4, "USD"), b"USD\0");
+        encoding(&tinystr!(8, "en-US"), b"en-US\0\0\0");
+        encoding(&tinystr!(16, "strict-encoding"), b"strict-encoding\0");
+        encoding(&TinyStr4::from_bytes(b"abcd").unwrap(), b"abcd");
+        assert_eq!(TinyStr4::ENCODED_LEN, 4);
+        assert_eq!(TinyStr8::ENCODED_LEN, 8);
+        assert_eq!(tinystr!(16, "strict").encoded_size(), 16);
+    }
+
+    #[test]
+    fn invalid() {
+        let mut reader = StrictReader::in_memory(b"ab\xC3\xA9".to_vec(), 4);
+        assert!(matches!(TinyStr4::strict_decode(&mut reader), Err(DecodeError::Ascii(_))));
+        let mut reader = StrictReader::in_memory(b"ab\0cdefg".to_vec(), 8);
+        assert!(matches!(
+            TinyStr8::strict_decode(&mut reader),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+    }
+}
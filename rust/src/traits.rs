@@ -19,17 +19,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fmt::Debug;
 use std::io::{BufRead, Seek};
-use std::{fs, io};
+use std::{any, fs, io};
 
-use amplify::confinement::{Collection, Confined};
+use amplify::confinement::{self, Collection, Confined};
 use amplify::num::u24;
 use amplify::Wrapper;
 
 use super::DecodeError;
+use crate::writer::{strict_encode_into, FixedLen};
 use crate::{
-    DeserializeError, FieldName, Primitive, SerializeError, Sizing, StrictDumb, StrictEnum,
-    StrictReader, StrictStruct, StrictSum, StrictTuple, StrictType, StrictUnion, StrictWriter,
+    DeserializeError, EncodeError, FieldName, Primitive, SerializeError, Sizing, StrictDumb,
+    StrictEncodeSize, StrictEncodedLen, StrictEnum, StrictReader, StrictStruct, StrictSum,
+    StrictTuple, StrictType, StrictUnion, StrictWriter,
 };
 
 pub trait TypedParent: Sized {}
@@ -182,14 +185,24 @@ pub trait TypedRead: Sized {
 
     #[doc(hidden)]
     unsafe fn _read_raw_len<const MAX_LEN: usize>(&mut self) -> Result<usize, DecodeError> {
-        Ok(match MAX_LEN {
+        let len = match MAX_LEN {
             tiny if tiny <= u8::MAX as usize => u8::strict_decode(self)? as usize,
             small if small <= u16::MAX as usize => u16::strict_decode(self)? as usize,
             medium if medium <= u24::MAX.into_usize() => u24::strict_decode(self)?.into_usize(),
             large if large <= u32::MAX as usize => u32::strict_decode(self)? as usize,
             huge if huge <= u64::MAX as usize => u64::strict_decode(self)? as usize,
             _ => unreachable!("confined collections larger than u64::MAX must not exist"),
-        })
+        };
+        // Checked before any item is read, such that a malicious length prefix can't
+        // cause a large allocation
+        if len > MAX_LEN {
+            return Err(confinement::Error::Oversize {
+                len,
+                max_len: MAX_LEN,
+            }
+            .into());
+        }
+        Ok(len)
     }
 
     #[doc(hidden)]
@@ -224,11 +237,76 @@ pub trait DefineStruct: Sized {
 pub trait WriteStruct: Sized {
     type Parent: TypedParent;
     fn write_field(self, name: FieldName, value: &impl StrictEncode) -> io::Result<Self>;
+
+    /// Writes items of a collection field without a length prefix, since the
+    /// number of the items is held by another field.
+    fn write_counted<C: CountedItems>(self, name: FieldName, items: &C) -> io::Result<Self> {
+        items
+            .counted_items()
+            .iter()
+            .try_fold(self, |writer, item| writer.write_field(name.clone(), item))
+    }
+
     fn complete(self) -> Self::Parent;
 }
 
 pub trait ReadStruct {
     fn read_field<T: StrictDecode>(&mut self, field: FieldName) -> Result<T, DecodeError>;
+
+    /// Reads `count` items of a collection field encoded without a length
+    /// prefix, failing before reading the items if `count` exceeds the
+    /// collection capacity.
+    fn read_counted<C: CountedItems>(
+        &mut self,
+        field: FieldName,
+        count: usize,
+    ) -> Result<C, DecodeError> {
+        C::check_count(count)?;
+        let items = (0..count)
+            .map(|_| self.read_field(field.clone()))
+            .collect::<Result<_, _>>()?;
+        C::from_counted_items(items)
+    }
+
+    /// Marks field which is absent from the encoded data (for instance, a
+    /// conditional field of a tagged union) as processed without reading it.
+    fn skip_field(&mut self, field: FieldName) { let _ = field; }
+}
+
+/// Collection which can be encoded without a length prefix when the number of
+/// its items is held by another field of the same structure.
+pub trait CountedItems: Sized {
+    type Item: StrictEncode + StrictDecode;
+
+    fn counted_items(&self) -> &[Self::Item];
+
+    /// Fails if the collection can't hold `count` items.
+    fn check_count(count: usize) -> Result<(), DecodeError>;
+
+    fn from_counted_items(items: Vec<Self::Item>) -> Result<Self, DecodeError>;
+}
+
+impl<T: StrictEncode + StrictDecode, const MIN_LEN: usize, const MAX_LEN: usize> CountedItems
+    for Confined<Vec<T>, MIN_LEN, MAX_LEN>
+{
+    type Item = T;
+
+    fn counted_items(&self) -> &[T] { self.as_slice() }
+
+    fn check_count(count: usize) -> Result<(), DecodeError> {
+        if count > MAX_LEN {
+            return Err(confinement::Error::Oversize {
+                len: count,
+                max_len: MAX_LEN,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    fn from_counted_items(items: Vec<T>) -> Result<Self, DecodeError> {
+        Confined::try_from(items).map_err(DecodeError::from)
+    }
 }
 
 pub trait DefineEnum: Sized {
@@ -336,6 +414,41 @@ impl<T: StrictEncode> StrictEncode for &T {
     }
 }
 
+/// Encoding of small [`Copy`] values, which are taken by value instead of a
+/// reference. Implemented for all [`Copy`] types supporting [`StrictEncode`].
+pub trait StrictEncodeRef: Copy + StrictEncode {
+    /// Strict-encodes the value into the writer, returning the number of bytes
+    /// written.
+    fn strict_encode_val(self, writer: &mut impl io::Write) -> io::Result<usize> {
+        self.strict_write(usize::MAX, writer)
+    }
+}
+
+impl<T: Copy + StrictEncode> StrictEncodeRef for T {}
+
+/// Types with a fixed encoding length, which can be encoded directly into a
+/// pre-allocated buffer, like a part of a network packet.
+///
+/// The provided method writes the value with its [`StrictEncode`]
+/// implementation; types which encoding is their in-memory representation,
+/// like byte arrays, override it with a plain copy.
+pub trait StrictEncodeInplace: StrictEncodedLen {
+    /// Encodes the value into `buf`. The buffer length `N` must be equal to
+    /// [`StrictEncodedLen::ENCODED_LEN`], which is checked at compile time.
+    fn strict_encode_inplace<const N: usize>(&self, buf: &mut [u8; N]) -> Result<(), EncodeError> {
+        let _ = FixedLen::<Self, N>::LEN;
+        strict_encode_into(self, buf)
+    }
+}
+
+impl<const LEN: usize> StrictEncodeInplace for [u8; LEN] {
+    fn strict_encode_inplace<const N: usize>(&self, buf: &mut [u8; N]) -> Result<(), EncodeError> {
+        let _ = FixedLen::<Self, N>::LEN;
+        buf.copy_from_slice(self);
+        Ok(())
+    }
+}
+
 pub trait StrictSerialize: StrictEncode {
     fn strict_serialized_len(&self) -> io::Result<usize> {
         let counter = StrictWriter::counter();
@@ -387,3 +500,65 @@ pub trait StrictDeserialize: StrictDecode {
         Ok(me)
     }
 }
+
+/// Declares version of the wire format used by a type, allowing to check
+/// compatibility of independently evolving protocol types.
+///
+/// Can be derived with `#[strict_type(wire_version = N)]` attribute when
+/// deriving [`StrictType`].
+pub trait WireFormat {
+    const WIRE_VERSION: u16;
+}
+
+/// Panics if types `A` and `B` declare different wire format versions.
+pub fn assert_wire_compatible<A: WireFormat, B: WireFormat>() {
+    assert_eq!(
+        A::WIRE_VERSION,
+        B::WIRE_VERSION,
+        "wire format version of `{}` does not match the one of `{}`",
+        any::type_name::<A>(),
+        any::type_name::<B>()
+    );
+}
+
+/// Panics if the value doesn't decode back into itself from its strict
+/// encoding, or if its [`StrictEncodeSize::encoded_size`] differs from the
+/// length of the encoded data.
+///
+/// Used by the tests generated with `#[derive(StrictEncodeCompatTest)]`.
+pub fn assert_encoding_compat<T>(val: &T)
+where T: StrictEncodeSize + StrictDecode + PartialEq + Debug {
+    let name = any::type_name::<T>();
+    let data = val
+        .strict_encode(StrictWriter::in_memory(usize::MAX))
+        .unwrap_or_else(|err| panic!("unable to encode `{name}`: {err}"))
+        .unbox();
+    let len = data.len();
+    assert_eq!(
+        val.encoded_size(),
+        len,
+        "encoded size of `{name}` doesn't match the length of its encoding"
+    );
+    let mut reader = StrictReader::in_memory(data, len);
+    let decoded = T::strict_decode(&mut reader)
+        .unwrap_or_else(|err| panic!("unable to decode `{name}`: {err}"));
+    assert_eq!(
+        reader.unbox().position() as usize,
+        len,
+        "`{name}` encoding is not entirely consumed"
+    );
+    assert_eq!(&decoded, val, "`{name}` doesn't decode back into the encoded value");
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encode_val() {
+        let mut buf = vec![0xFF];
+        assert_eq!(0x1234u16.strict_encode_val(&mut buf).unwrap(), 2);
+        assert_eq!([1u8, 2, 3].strict_encode_val(&mut buf).unwrap(), 3);
+        assert_eq!(buf, [0xFF, 0x34, 0x12, 1, 2, 3]);
+    }
+}
@@ -22,8 +22,9 @@
 use std::any;
 use std::collections::BTreeSet;
 use std::fmt::{Debug, Display};
+use std::marker::PhantomData;
 
-use crate::{FieldName, LibName, TypeName};
+use crate::{FieldName, LibName, SchemaHasher, TypeName};
 
 #[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
 #[display("unexpected variant {1} for enum or union {0:?}")]
@@ -64,10 +65,28 @@ pub trait StrictType: Sized {
         }
         Some(tn!(ident.join("")))
     }
+
+    /// Feeds the type schema into the `hasher` computing [`schema_hash`].
+    ///
+    /// Defaults to the library and type name, which is enough for the types
+    /// having unique names. Types without a name, like collections, hash the
+    /// schemata of the types they contain instead.
+    ///
+    /// The marker argument makes the bounds implied by the type declaration
+    /// (like `T: 'a` for a `&'a T` field) available to the implementation,
+    /// which older compilers require to name the field types.
+    ///
+    /// [`schema_hash`]: crate::schema_hash
+    fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) {
+        hasher.write_name::<Self>()
+    }
 }
 
 impl<T: StrictType> StrictType for &T {
     const STRICT_LIB_NAME: &'static str = T::STRICT_LIB_NAME;
+    fn strict_schema(hasher: &mut SchemaHasher, _: PhantomData<Self>) {
+        T::strict_schema(hasher, PhantomData)
+    }
 }
 
 pub trait StrictProduct: StrictType + StrictDumb {}
@@ -211,6 +230,18 @@ where
     }
 }
 
+/// Assertion that the field of a Rust `union` specified with
+/// `#[strict_type(union_field = "...")]` attribute is always the initialized
+/// one, which is required by derived strict encoding of the union.
+///
+/// # Safety
+///
+/// Derived [`StrictEncode`](crate::StrictEncode) and
+/// [`StrictEncodeSize`](crate::StrictEncodeSize) implementations read the
+/// field without any checks. Implementing this trait for a union whose values
+/// may have a different field initialized leads to undefined behavior.
+pub unsafe trait ActiveUnionField {}
+
 pub enum TypeClass {
     Embedded,
     Enum(&'static [(u8, &'static str)]),
@@ -23,7 +23,13 @@ use std::cmp::Ordering;
 use std::fmt::{self, Display, Formatter};
 use std::io;
 
-use crate::{ReadStruct, VariantName, WriteStruct, STRICT_TYPES_LIB};
+use amplify::confinement::SmallBlob;
+
+use crate::{
+    DecodeError, ReadStruct, StrictDecode, StrictDumb, StrictEncode, StrictEncodeSize,
+    StrictEncodedLen, StrictProduct, StrictReader, StrictStruct, StrictType, StrictWriter,
+    TypedRead, TypedWrite, VariantName, WriteStruct, LIB_NAME_STD, STRICT_TYPES_LIB,
+};
 
 // TODO: Control that min > max!
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
@@ -82,6 +88,158 @@ impl Display for Sizing {
     }
 }
 
+/// Pair of minimal and maximal values, guaranteed to have `min <= max`.
+///
+/// Encoded as a structure of two consecutive `T` values; decoding fails with
+/// [`DecodeError::InvertedMinMax`] if the minimum exceeds the maximum.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct StrictMinMax<T: Ord> {
+    min: T,
+    max: T,
+}
+
+impl<T: Ord> StrictMinMax<T> {
+    /// Constructs min-max pair, returning `None` if `min > max`.
+    pub fn new(min: T, max: T) -> Option<Self> {
+        if min > max {
+            return None;
+        }
+        Some(StrictMinMax { min, max })
+    }
+
+    pub fn min(&self) -> &T { &self.min }
+
+    pub fn max(&self) -> &T { &self.max }
+
+    pub fn into_inner(self) -> (T, T) { (self.min, self.max) }
+
+    pub fn contains(&self, value: &T) -> bool { value >= &self.min && value <= &self.max }
+}
+
+impl<T: Ord + Clone> StrictMinMax<T> {
+    /// Restricts `value` to the `min..=max` range.
+    pub fn clamp(&self, value: T) -> T { value.clamp(self.min.clone(), self.max.clone()) }
+}
+
+impl<T: Ord + StrictDumb> StrictDumb for StrictMinMax<T> {
+    fn strict_dumb() -> Self {
+        StrictMinMax {
+            min: T::strict_dumb(),
+            max: T::strict_dumb(),
+        }
+    }
+}
+impl<T: Ord + StrictType> StrictType for StrictMinMax<T> {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+}
+impl<T: Ord + StrictType + StrictDumb> StrictProduct for StrictMinMax<T> {}
+impl<T: Ord + StrictType + StrictDumb> StrictStruct for StrictMinMax<T> {
+    const ALL_FIELDS: &'static [&'static str] = &["min", "max"];
+}
+impl<T: Ord + StrictEncode + StrictDumb> StrictEncode for StrictMinMax<T> {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_struct::<Self>(|w| {
+            Ok(w.write_field(fname!("min"), &self.min)?
+                .write_field(fname!("max"), &self.max)?
+                .complete())
+        })
+    }
+}
+impl<T: Ord + StrictDecode + StrictDumb> StrictDecode for StrictMinMax<T> {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_struct(|r| {
+            let min = r.read_field(fname!("min"))?;
+            let max = r.read_field(fname!("max"))?;
+            StrictMinMax::new(min, max).ok_or(DecodeError::InvertedMinMax)
+        })
+    }
+}
+impl<T: Ord + StrictEncodeSize + StrictDumb> StrictEncodeSize for StrictMinMax<T> {
+    fn encoded_size(&self) -> usize { self.min.encoded_size() + self.max.encoded_size() }
+}
+impl<T: Ord + StrictEncodedLen + StrictDumb> StrictEncodedLen for StrictMinMax<T> {
+    const ENCODED_LEN: usize = 2 * T::ENCODED_LEN;
+}
+
+/// Self-describing field, encoded as a structure of the `TYPE_ID` byte
+/// followed by the strict encoding of the value, prefixed with its length as
+/// a 16-bit number.
+///
+/// Decoding fails with [`DecodeError::UnexpectedHeaderType`] if the type id
+/// doesn't match `TYPE_ID`, and with [`DecodeError::DataIntegrityError`] if the
+/// value doesn't take exactly the number of bytes given by its length.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct HeaderField<T, const TYPE_ID: u8> {
+    pub value: T,
+}
+
+impl<T, const TYPE_ID: u8> HeaderField<T, TYPE_ID> {
+    pub const TYPE_ID: u8 = TYPE_ID;
+
+    pub fn new(value: T) -> Self { HeaderField { value } }
+
+    pub fn into_inner(self) -> T { self.value }
+}
+
+impl<T: StrictEncode + StrictDumb, const TYPE_ID: u8> HeaderField<T, TYPE_ID> {
+    /// Encodes all the fields one after another.
+    pub fn encode_many<W: TypedWrite>(fields: &[Self], mut writer: W) -> io::Result<W> {
+        for field in fields {
+            writer = field.strict_encode(writer)?;
+        }
+        Ok(writer)
+    }
+}
+
+impl<T: StrictDumb, const TYPE_ID: u8> StrictDumb for HeaderField<T, TYPE_ID> {
+    fn strict_dumb() -> Self { HeaderField::new(T::strict_dumb()) }
+}
+impl<T: StrictType, const TYPE_ID: u8> StrictType for HeaderField<T, TYPE_ID> {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+}
+impl<T: StrictType + StrictDumb, const TYPE_ID: u8> StrictProduct for HeaderField<T, TYPE_ID> {}
+impl<T: StrictType + StrictDumb, const TYPE_ID: u8> StrictStruct for HeaderField<T, TYPE_ID> {
+    const ALL_FIELDS: &'static [&'static str] = &["typeId", "value"];
+}
+impl<T: StrictEncode + StrictDumb, const TYPE_ID: u8> StrictEncode for HeaderField<T, TYPE_ID> {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        let data = self
+            .value
+            .strict_encode(StrictWriter::in_memory(u16::MAX as usize))?
+            .unbox();
+        let data = SmallBlob::try_from(data)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+        writer.write_struct::<Self>(|w| {
+            Ok(w.write_field(fname!("typeId"), &TYPE_ID)?
+                .write_field(fname!("value"), &data)?
+                .complete())
+        })
+    }
+}
+impl<T: StrictDecode + StrictDumb, const TYPE_ID: u8> StrictDecode for HeaderField<T, TYPE_ID> {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_struct(|r| {
+            let found: u8 = r.read_field(fname!("typeId"))?;
+            if found != TYPE_ID {
+                return Err(DecodeError::UnexpectedHeaderType {
+                    expected: TYPE_ID,
+                    found,
+                });
+            }
+            let data: SmallBlob = r.read_field(fname!("value"))?;
+            let len = data.len();
+            let mut reader = StrictReader::in_memory(data.into_inner(), len);
+            let value = T::strict_decode(&mut reader)?;
+            if reader.unbox().position() as usize != len {
+                return Err(DecodeError::DataIntegrityError(format!(
+                    "header field value doesn't take all {len} bytes given by its length"
+                )));
+            }
+            Ok(HeaderField::new(value))
+        })
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(crate = "serde_crate"))]
 pub struct Variant {
@@ -130,3 +288,51 @@ impl Display for Variant {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::encoding;
+
+    #[test]
+    fn min_max() {
+        let range = StrictMinMax::new(2u8, 16).unwrap();
+        encoding(&range, [0x02, 0x10]);
+        assert_eq!(StrictMinMax::<u32>::ENCODED_LEN, 8);
+        assert_eq!(StrictMinMax::new(1u32, 2).unwrap().encoded_size(), 8);
+        assert_eq!(range.clamp(0), 2);
+        assert_eq!(range.clamp(8), 8);
+        assert_eq!(range.clamp(32), 16);
+        assert!(StrictMinMax::new(3u8, 2).is_none());
+
+        let mut reader = StrictReader::in_memory(vec![0x10, 0x02], 2);
+        assert_eq!(
+            StrictMinMax::<u8>::strict_decode(&mut reader),
+            Err(DecodeError::InvertedMinMax)
+        );
+    }
+
+    #[test]
+    fn header_field() {
+        let field = HeaderField::<u32, 7>::new(0xCAFE_BABE);
+        encoding(&field, [0x07, 0x04, 0x00, 0xBE, 0xBA, 0xFE, 0xCA]);
+
+        let fields = [HeaderField::<u8, 1>::new(0xA5), HeaderField::new(0x5A)];
+        let writer = HeaderField::encode_many(&fields, StrictWriter::in_memory(16)).unwrap();
+        assert_eq!(writer.unbox(), [0x01, 0x01, 0x00, 0xA5, 0x01, 0x01, 0x00, 0x5A]);
+
+        let mut reader = StrictReader::in_memory(vec![0x02, 0x01, 0x00, 0xA5], 4);
+        assert_eq!(
+            HeaderField::<u8, 1>::strict_decode(&mut reader),
+            Err(DecodeError::UnexpectedHeaderType {
+                expected: 1,
+                found: 2
+            })
+        );
+        let mut reader = StrictReader::in_memory(vec![0x01, 0x02, 0x00, 0xA5, 0x00], 5);
+        assert!(matches!(
+            HeaderField::<u8, 1>::strict_decode(&mut reader),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+    }
+}
@@ -0,0 +1,291 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tracing of the encoded and decoded structure fields for protocol
+//! debugging.
+//!
+//! Code generated by the derive macros reports the start and the end of each
+//! structure field with [`field_start`] and [`field_end`] hooks. Without
+//! `wire_watch` feature the hooks do nothing; with it they record the field
+//! boundaries for a [`WireWatcher`] active on the same thread.
+
+#[cfg(feature = "wire_watch")]
+use std::cell::{Cell, RefCell};
+#[cfg(feature = "wire_watch")]
+use std::io;
+
+#[cfg(feature = "wire_watch")]
+enum Marker {
+    Start {
+        name: &'static str,
+        type_name: &'static str,
+    },
+    End,
+}
+
+#[cfg(feature = "wire_watch")]
+thread_local! {
+    static ACTIVE: Cell<usize> = const { Cell::new(0) };
+    static MARKERS: RefCell<Vec<Marker>> = const { RefCell::new(Vec::new()) };
+}
+
+#[cfg(feature = "wire_watch")]
+fn push_marker(marker: Marker) {
+    if ACTIVE.with(Cell::get) > 0 {
+        MARKERS.with(|markers| markers.borrow_mut().push(marker));
+    }
+}
+
+/// Reports that encoding or decoding of the field `name` having type
+/// `type_name` is started.
+#[doc(hidden)]
+#[inline]
+#[cfg_attr(not(feature = "wire_watch"), allow(unused_variables))]
+pub fn field_start(name: &'static str, type_name: &'static str) {
+    #[cfg(feature = "wire_watch")]
+    push_marker(Marker::Start { name, type_name });
+}
+
+/// Reports that encoding or decoding of the last started field is completed.
+#[doc(hidden)]
+#[inline]
+pub fn field_end() {
+    #[cfg(feature = "wire_watch")]
+    push_marker(Marker::End);
+}
+
+/// Direction of the data passing through a [`WireWatcher`].
+#[cfg(feature = "wire_watch")]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum WireDirection {
+    Encode,
+    Decode,
+}
+
+/// Information about a single encoded or decoded structure field.
+#[cfg(feature = "wire_watch")]
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct WireEvent {
+    /// Dot-separated names of the field and all of its parent fields.
+    pub field_path: String,
+    pub direction: WireDirection,
+    /// Data of the field, including the data of its nested fields.
+    pub bytes: Vec<u8>,
+    /// Rust type name of the field.
+    pub type_name: &'static str,
+}
+
+#[cfg(feature = "wire_watch")]
+struct Frame {
+    path: String,
+    type_name: &'static str,
+    bytes: Vec<u8>,
+}
+
+/// Wrapper around [`io::Write`] or [`io::Read`] reporting a [`WireEvent`]
+/// to the `log` closure for each structure field with derived strict encoding
+/// which passes through it.
+///
+/// Field boundaries are reported by the derived code through thread-local
+/// state, thus only a single watcher may be used on a thread at a time, and
+/// the data must not be buffered between the watcher and the strict writer or
+/// reader. Events are delivered on the next read or write, so the events for
+/// the last fields are delivered by [`WireWatcher::finish`] (or on drop).
+#[cfg(feature = "wire_watch")]
+pub struct WireWatcher<Inner, Log: FnMut(WireEvent)> {
+    inner: Option<Inner>,
+    log: Log,
+    direction: WireDirection,
+    frames: Vec<Frame>,
+}
+
+#[cfg(feature = "wire_watch")]
+impl<Inner, Log: FnMut(WireEvent)> WireWatcher<Inner, Log> {
+    fn with(inner: Inner, log: Log, direction: WireDirection) -> Self {
+        ACTIVE.with(|active| active.set(active.get() + 1));
+        MARKERS.with(|markers| markers.borrow_mut().clear());
+        WireWatcher {
+            inner: Some(inner),
+            log,
+            direction,
+            frames: vec![],
+        }
+    }
+
+    /// Constructs watcher over a writer.
+    pub fn encoder(inner: Inner, log: Log) -> Self
+    where Inner: io::Write {
+        Self::with(inner, log, WireDirection::Encode)
+    }
+
+    /// Constructs watcher over a reader.
+    pub fn decoder(inner: Inner, log: Log) -> Self
+    where Inner: io::Read {
+        Self::with(inner, log, WireDirection::Decode)
+    }
+
+    /// Delivers the pending events and returns the wrapped writer or reader.
+    pub fn finish(mut self) -> Inner {
+        self.process_markers();
+        self.inner.take().expect("inner is present until finished")
+    }
+
+    fn inner(&mut self) -> &mut Inner {
+        self.inner
+            .as_mut()
+            .expect("inner is present until finished")
+    }
+
+    fn process_markers(&mut self) {
+        let markers = MARKERS.with(|markers| std::mem::take(&mut *markers.borrow_mut()));
+        for marker in markers {
+            match marker {
+                Marker::Start { name, type_name } => {
+                    let path = match self.frames.last() {
+                        None => name.to_owned(),
+                        Some(parent) => format!("{}.{name}", parent.path),
+                    };
+                    self.frames.push(Frame {
+                        path,
+                        type_name,
+                        bytes: vec![],
+                    });
+                }
+                Marker::End => {
+                    let Some(frame) = self.frames.pop() else {
+                        continue;
+                    };
+                    if let Some(parent) = self.frames.last_mut() {
+                        parent.bytes.extend(&frame.bytes);
+                    }
+                    (self.log)(WireEvent {
+                        field_path: frame.path,
+                        direction: self.direction,
+                        bytes: frame.bytes,
+                        type_name: frame.type_name,
+                    });
+                }
+            }
+        }
+    }
+
+    fn record(&mut self, bytes: &[u8]) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.bytes.extend(bytes);
+        }
+    }
+}
+
+#[cfg(feature = "wire_watch")]
+impl<Inner, Log: FnMut(WireEvent)> Drop for WireWatcher<Inner, Log> {
+    fn drop(&mut self) {
+        self.process_markers();
+        ACTIVE.with(|active| active.set(active.get() - 1));
+    }
+}
+
+#[cfg(feature = "wire_watch")]
+impl<Inner: io::Write, Log: FnMut(WireEvent)> io::Write for WireWatcher<Inner, Log> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.process_markers();
+        let len = self.inner().write(buf)?;
+        self.record(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> { self.inner().flush() }
+}
+
+#[cfg(feature = "wire_watch")]
+impl<Inner: io::Read, Log: FnMut(WireEvent)> io::Read for WireWatcher<Inner, Log> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.process_markers();
+        let len = self.inner().read(buf)?;
+        self.record(&buf[..len]);
+        Ok(len)
+    }
+}
+
+#[cfg(all(test, feature = "wire_watch"))]
+mod test {
+    use std::any;
+
+    use super::*;
+    use crate::{
+        strict_encode_to_writer, StrictDecode, StrictEncode, StrictReader, StrictType, LIB_NAME_STD,
+    };
+
+    #[derive(Clone, Eq, PartialEq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, crate = crate)]
+    struct Header {
+        version: u8,
+        len: u16,
+    }
+
+    #[derive(Clone, Eq, PartialEq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = LIB_NAME_STD, crate = crate)]
+    struct Message {
+        header: Header,
+        flags: u8,
+    }
+
+    fn expected(direction: WireDirection) -> Vec<WireEvent> {
+        let event = |field_path: &str, bytes: &[u8], type_name| WireEvent {
+            field_path: field_path.to_owned(),
+            direction,
+            bytes: bytes.to_vec(),
+            type_name,
+        };
+        vec![
+            event("header.version", &[1], "u8"),
+            event("header.len", &[3, 2], "u16"),
+            event("header", &[1, 3, 2], any::type_name::<Header>()),
+            event("flags", &[0xFF], "u8"),
+        ]
+    }
+
+    #[test]
+    fn watch() {
+        let msg = Message {
+            header: Header {
+                version: 1,
+                len: 0x0203,
+            },
+            flags: 0xFF,
+        };
+
+        let mut events = vec![];
+        let mut watcher = WireWatcher::encoder(vec![], |event| events.push(event));
+        strict_encode_to_writer(&msg, &mut watcher).unwrap();
+        let data = watcher.finish();
+        assert_eq!(data, [1, 3, 2, 0xFF]);
+        assert_eq!(events, expected(WireDirection::Encode));
+
+        let mut events = vec![];
+        let mut watcher = WireWatcher::decoder(io::Cursor::new(data), |event| events.push(event));
+        let decoded = Message::strict_decode(&mut StrictReader::with(usize::MAX, &mut watcher));
+        watcher.finish();
+        assert_eq!(decoded.unwrap(), msg);
+        assert_eq!(events, expected(WireDirection::Decode));
+    }
+}
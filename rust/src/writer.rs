@@ -27,9 +27,10 @@ use std::marker::PhantomData;
 use amplify::WriteCounter;
 
 use crate::{
-    DefineEnum, DefineStruct, DefineTuple, DefineUnion, FieldName, LibName, StrictEncode,
-    StrictEnum, StrictStruct, StrictSum, StrictTuple, StrictUnion, TypeName, TypedParent,
-    TypedWrite, Variant, VariantName, WriteEnum, WriteStruct, WriteTuple, WriteUnion, LIB_EMBEDDED,
+    DefineEnum, DefineStruct, DefineTuple, DefineUnion, EncodeError, FieldName, LibName,
+    StrictEncode, StrictEncodedLen, StrictEnum, StrictStruct, StrictSum, StrictTuple, StrictUnion,
+    TypeName, TypedParent, TypedWrite, Variant, VariantName, WriteEnum, WriteStruct, WriteTuple,
+    WriteUnion, LIB_EMBEDDED,
 };
 
 // TODO: Move to amplify crate
@@ -75,6 +76,204 @@ impl<W: io::Write> io::Write for CountingWriter<W> {
     fn flush(&mut self) -> io::Result<()> { self.writer.flush() }
 }
 
+/// Writer forwarding all data to two writers at once, for instance to send the
+/// encoded data over network and compute their hash in a single pass.
+#[derive(Debug)]
+pub struct TeeWriter<A: io::Write, B: io::Write> {
+    primary: A,
+    secondary: B,
+}
+
+impl<A: io::Write, B: io::Write> TeeWriter<A, B> {
+    pub fn new(primary: A, secondary: B) -> Self { Self { primary, secondary } }
+
+    pub fn unbox(self) -> (A, B) { (self.primary, self.secondary) }
+}
+
+impl<A: io::Write, B: io::Write> io::Write for TeeWriter<A, B> {
+    /// Writes data to the primary writer, and then exactly the same amount of
+    /// data, which was accepted by the primary writer, to the secondary one.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let count = self.primary.write(buf)?;
+        self.secondary.write_all(&buf[..count])?;
+        Ok(count)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.primary.flush()?;
+        self.secondary.flush()
+    }
+}
+
+/// Writer buffering all the data in memory and passing them to the inner
+/// writer only on [`TransactionalEncoder::commit`], such that data of a value
+/// which failed to encode partway never reach the inner writer.
+///
+/// The buffered data are discarded on [`TransactionalEncoder::rollback`] or
+/// when the encoder is dropped without being committed.
+#[derive(Debug)]
+pub struct TransactionalEncoder<W: io::Write + io::Seek> {
+    buffer: Vec<u8>,
+    writer: W,
+}
+
+impl<W: io::Write + io::Seek> TransactionalEncoder<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            buffer: vec![],
+            writer,
+        }
+    }
+
+    /// Returns data written since the encoder was created.
+    pub fn buffered(&self) -> &[u8] { &self.buffer }
+
+    /// Writes all buffered data to the inner writer and returns it.
+    ///
+    /// If the inner writer fails, it is rewound to the position it had before
+    /// the commit, such that the partially written data get overwritten by the
+    /// next write.
+    pub fn commit(self) -> io::Result<W> {
+        let Self { buffer, mut writer } = self;
+        let pos = writer.stream_position()?;
+        if let Err(err) = writer.write_all(&buffer).and_then(|_| writer.flush()) {
+            writer.seek(io::SeekFrom::Start(pos))?;
+            return Err(err);
+        }
+        Ok(writer)
+    }
+
+    /// Discards all buffered data and returns the inner writer untouched.
+    pub fn rollback(self) -> W { self.writer }
+}
+
+impl<W: io::Write + io::Seek> io::Write for TransactionalEncoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.buffer.write(buf) }
+
+    fn flush(&mut self) -> io::Result<()> { Ok(()) }
+}
+
+/// Strict-encodes the value into the writer only if the whole value was
+/// encoded successfully, returning the number of bytes written. On error
+/// nothing is written to the writer.
+///
+/// See [`TransactionalEncoder`] for the details.
+pub fn strict_encode_transactional<T: StrictEncode, W: io::Write + io::Seek>(
+    value: &T,
+    writer: &mut W,
+) -> io::Result<usize> {
+    let mut encoder = TransactionalEncoder::new(writer);
+    let count = strict_encode_to_writer(value, &mut encoder)?;
+    encoder.commit()?;
+    Ok(count)
+}
+
+/// Strict-encodes the value into the writer, returning the number of bytes
+/// written.
+///
+/// Same as [`StrictEncode::strict_write`] without a limit on the data size,
+/// which can be called without importing the trait.
+pub fn strict_encode_to_writer<T: StrictEncode, W: io::Write>(
+    value: &T,
+    writer: &mut W,
+) -> io::Result<usize> {
+    value.strict_write(usize::MAX, writer)
+}
+
+/// Compile-time check that the array length `N` matches the fixed encoding
+/// length of the type `T`.
+pub(crate) struct FixedLen<T, const N: usize>(PhantomData<T>);
+
+impl<T: StrictEncodedLen, const N: usize> FixedLen<T, N> {
+    pub(crate) const LEN: usize = {
+        assert!(
+            T::ENCODED_LEN == N,
+            "array length must match the fixed encoding length of the type"
+        );
+        N
+    };
+}
+
+/// Strict-encodes the value into `buf`, which must have exactly the size of
+/// the value encoding.
+pub(crate) fn strict_encode_into<T: StrictEncode>(
+    value: &T,
+    buf: &mut [u8],
+) -> Result<(), EncodeError> {
+    let expected = buf.len();
+    let actual = value
+        .strict_encode(StrictWriter::with(expected, buf))?
+        .count();
+    if actual != expected {
+        return Err(EncodeError::FixedLenMismatch { expected, actual });
+    }
+    Ok(())
+}
+
+/// Strict-encodes a value of a type with a fixed encoding length into a
+/// stack-allocated array, without any heap allocation.
+///
+/// The array length `N` must be equal to [`StrictEncodedLen::ENCODED_LEN`] of
+/// the type, which is checked at compile time:
+///
+/// ```
+/// let data: [u8; 4] = strict_encoding::strict_encode_fixed(&0xCAFE_BABEu32).unwrap();
+/// assert_eq!(data, [0xBE, 0xBA, 0xFE, 0xCA]);
+/// ```
+///
+/// ```compile_fail
+/// let data: [u8; 3] = strict_encoding::strict_encode_fixed(&0xCAFE_BABEu32).unwrap();
+/// ```
+pub fn strict_encode_fixed<T: StrictEncode + StrictEncodedLen, const N: usize>(
+    value: &T,
+) -> Result<[u8; N], EncodeError> {
+    let _ = FixedLen::<T, N>::LEN;
+    let mut buf = [0u8; N];
+    strict_encode_into(value, &mut buf)?;
+    Ok(buf)
+}
+
+macro_rules! encode_le {
+    ($($(#[$attr:meta])* $name:ident: $ty:ty),+ $(,)?) => {
+        $(
+            $(#[$attr])*
+            fn $name(&mut self, v: $ty) -> io::Result<()> { self.write_all(&v.to_le_bytes()) }
+        )+
+    };
+}
+
+/// Methods writing strict-encoded primitive values directly to any
+/// [`io::Write`], for use in manual [`StrictEncode`] implementations.
+pub trait StrictWriteExt: io::Write {
+    encode_le! {
+        /// Writes `u8` value.
+        encode_u8: u8,
+        /// Writes `u16` value in little-endian byte order.
+        encode_u16_le: u16,
+        /// Writes `u32` value in little-endian byte order.
+        encode_u32_le: u32,
+        /// Writes `u64` value in little-endian byte order.
+        encode_u64_le: u64,
+        /// Writes `u128` value in little-endian byte order.
+        encode_u128_le: u128,
+        /// Writes `i8` value.
+        encode_i8: i8,
+        /// Writes `i16` value in little-endian byte order.
+        encode_i16_le: i16,
+        /// Writes `i32` value in little-endian byte order.
+        encode_i32_le: i32,
+        /// Writes `i64` value in little-endian byte order.
+        encode_i64_le: i64,
+        /// Writes `i128` value in little-endian byte order.
+        encode_i128_le: i128,
+    }
+
+    /// Writes `bool` value as a single byte `0` or `1`.
+    fn encode_bool(&mut self, v: bool) -> io::Result<()> { self.encode_u8(v as u8) }
+}
+
+impl<W: io::Write + ?Sized> StrictWriteExt for W {}
+
 #[derive(Debug, From)]
 pub struct StrictWriter<W: io::Write>(CountingWriter<W>);
 
@@ -620,3 +819,97 @@ impl<W: io::Write> StrictParent<W> for ParentDumb {
     fn from_write_split(_: StrictWriter<W>, _: Self::Remnant) -> Self { unreachable!() }
     fn into_write_split(self) -> (StrictWriter<W>, Self::Remnant) { unreachable!() }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::encode;
+    use crate::StrictType;
+
+    #[test]
+    fn write_ext() {
+        let mut data = vec![];
+        data.encode_u8(0xA5).unwrap();
+        data.encode_u16_le(0xCAFE).unwrap();
+        data.encode_i32_le(-2).unwrap();
+        data.encode_u64_le(1).unwrap();
+        data.encode_i128_le(i128::MIN).unwrap();
+        data.encode_bool(true).unwrap();
+
+        let mut expected = encode(&(0xA5u8, 0xCAFEu16, -2i32));
+        expected.extend(encode(&1u64));
+        expected.extend(encode(&i128::MIN));
+        expected.extend(encode(&true));
+        assert_eq!(data, expected);
+    }
+
+    struct Faulty;
+    impl StrictType for Faulty {
+        const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+    }
+    impl StrictEncode for Faulty {
+        fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+            let _ = 0xCAFEu16.strict_encode(writer)?;
+            Err(io::ErrorKind::InvalidInput.into())
+        }
+    }
+
+    #[test]
+    fn transactional() {
+        let mut cursor = io::Cursor::new(vec![0xA5]);
+        cursor.set_position(1);
+        assert!(strict_encode_to_writer(&Faulty, &mut cursor).is_err());
+        assert_eq!(cursor.get_ref(), &[0xA5, 0xFE, 0xCA]);
+
+        let mut cursor = io::Cursor::new(vec![0xA5]);
+        cursor.set_position(1);
+        assert!(strict_encode_transactional(&Faulty, &mut cursor).is_err());
+        assert_eq!(cursor.get_ref(), &[0xA5]);
+        assert_eq!(strict_encode_transactional(&0xCAFEu16, &mut cursor).unwrap(), 2);
+        assert_eq!(cursor.get_ref(), &[0xA5, 0xFE, 0xCA]);
+
+        let mut encoder = TransactionalEncoder::new(io::Cursor::new(vec![]));
+        strict_encode_to_writer(&true, &mut encoder).unwrap();
+        assert_eq!(encoder.buffered(), [1]);
+        assert!(encoder.rollback().get_ref().is_empty());
+    }
+
+    #[test]
+    fn encode_fixed() {
+        assert_eq!(strict_encode_fixed::<_, 2>(&0xCAFEu16).unwrap(), [0xFE, 0xCA]);
+        assert_eq!(strict_encode_fixed::<_, 3>(&[1u8, 2, 3]).unwrap(), [1, 2, 3]);
+        assert_eq!(
+            strict_encode_into(&0xCAFEu16, &mut [0u8; 3]),
+            Err(EncodeError::FixedLenMismatch {
+                expected: 3,
+                actual: 2
+            })
+        );
+        assert!(matches!(strict_encode_into(&0xCAFEu16, &mut [0u8; 1]), Err(EncodeError::Io(_))));
+    }
+
+    #[test]
+    fn encode_inplace() {
+        use crate::StrictEncodeInplace;
+
+        let mut packet = [0u8; 6];
+        let (head, tail) = packet.split_at_mut(2);
+        0xCAFEu16
+            .strict_encode_inplace(<&mut [u8; 2]>::try_from(head).unwrap())
+            .unwrap();
+        [1u8, 2, 3, 4]
+            .strict_encode_inplace(<&mut [u8; 4]>::try_from(tail).unwrap())
+            .unwrap();
+        assert_eq!(packet, [0xFE, 0xCA, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn tee_writer() {
+        let value = (0xA5u8, 0xCAFEu16);
+        let mut tee = TeeWriter::new(vec![], vec![]);
+        assert_eq!(strict_encode_to_writer(&value, &mut tee).unwrap(), 3);
+        let (primary, secondary) = tee.unbox();
+        assert_eq!(primary, encode(&value));
+        assert_eq!(secondary, primary);
+    }
+}
@@ -0,0 +1,101 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2023 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strict encoding for Diffie-Hellman keys from `x25519-dalek` crate.
+
+use std::io;
+
+use x25519_dalek::{EphemeralSecret, PublicKey};
+
+use crate::{
+    DecodeError, StrictDecode, StrictEncode, StrictEncodeSize, StrictEncodedLen, StrictType,
+    TypeName, TypedRead, TypedWrite,
+};
+
+/// [`PublicKey`] is encoded as its 32-byte Montgomery u-coordinate.
+impl StrictType for PublicKey {
+    const STRICT_LIB_NAME: &'static str = <[u8; Self::ENCODED_LEN]>::STRICT_LIB_NAME;
+    fn strict_name() -> Option<TypeName> { Some(tn!("X25519PublicKey")) }
+}
+impl StrictEncode for PublicKey {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        self.as_bytes().strict_encode(writer)
+    }
+}
+impl StrictDecode for PublicKey {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        <[u8; Self::ENCODED_LEN]>::strict_decode(reader).map(PublicKey::from)
+    }
+}
+impl StrictEncodeSize for PublicKey {
+    fn encoded_size(&self) -> usize { Self::ENCODED_LEN }
+}
+impl StrictEncodedLen for PublicKey {
+    const ENCODED_LEN: usize = 32;
+}
+
+/// [`EphemeralSecret`] is meant to be generated for a single key exchange and
+/// never leave the memory, so it does not expose its bytes and can't be
+/// decoded. The [`StrictEncode`] implementation exists only to make the
+/// mistake of encoding a structure containing the secret a runtime error
+/// ([`io::ErrorKind::Unsupported`]) instead of silently leaking it through
+/// some custom encoding.
+impl StrictType for EphemeralSecret {
+    const STRICT_LIB_NAME: &'static str = <[u8; 32]>::STRICT_LIB_NAME;
+    fn strict_name() -> Option<TypeName> { Some(tn!("X25519EphemeralSecret")) }
+}
+impl StrictEncode for EphemeralSecret {
+    fn strict_encode<W: TypedWrite>(&self, _writer: W) -> io::Result<W> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "ephemeral X25519 secret must not be encoded",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::hex::FromHex;
+
+    use super::*;
+    use crate::test::encoding;
+    use crate::StrictWriter;
+
+    // Alice's public key from RFC 7748, section 6.1
+    const PUBLIC: &str = "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a";
+
+    #[test]
+    fn public_key() {
+        let bytes = <[u8; 32]>::from_hex(PUBLIC).unwrap();
+        encoding(&PublicKey::from(bytes), bytes);
+        assert_eq!(PublicKey::strict_name(), Some(tn!("X25519PublicKey")));
+    }
+
+    #[test]
+    fn ephemeral_secret() {
+        let secret = EphemeralSecret::random_from_rng(rand::thread_rng());
+        let err = secret
+            .strict_encode(StrictWriter::in_memory(32))
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+        assert_eq!(EphemeralSecret::strict_name(), Some(tn!("X25519EphemeralSecret")));
+    }
+}